@@ -8,19 +8,34 @@
 use clap::{crate_version, App, Arg, ArgMatches, SubCommand};
 use color_eyre::eyre::{anyhow, Result};
 use colored::*;
-use compose::SystemOfSystems;
+use compose::{Matching, SystemOfSystems};
 use conserts_compose::compose;
 use conserts_elements::consert::Consert;
+use conserts_elements::evaluation::evaluate;
+use conserts_elements::uom::Quantities;
 use conserts_error::{self, CompileError, ConSertError};
+use conserts_parse::convert::Format;
+use conserts_parse::validate::Severity;
 use std::rc::Rc;
 
 mod compile;
+mod diagnostic;
 
 #[cfg(not(tarpaulin_include))] // IO function
 pub fn get_cli_parameters() -> clap::ArgMatches<'static> {
     let app = App::new("conserts")
-        .about("Parse, compile, or compose ConSert files")
+        .about("Parse, compile, evaluate, or compose ConSert files")
         .version(crate_version!())
+        .arg(
+            Arg::with_name("units")
+                .help("YAML or JSON file declaring quantities and units beyond those of uom::si")
+                .long("units")
+                .global(true)
+                .multiple(true)
+                .number_of_values(1)
+                .takes_value(true)
+                .value_name("FILE"),
+        )
         .subcommand(
             SubCommand::with_name("parse")
                 .about("Parses a ConSert and prints it on the CLI")
@@ -77,9 +92,72 @@ pub fn get_cli_parameters() -> clap::ArgMatches<'static> {
                         .value_name("FILTER-DEPTH"),
                 ),
         )
+        .subcommand(
+            SubCommand::with_name("convert")
+                .about("Converts a ConSert between the safeTbox XML, YAML and JSON formats")
+                .arg(
+                    Arg::with_name("input")
+                        .help("Input ConSert file")
+                        .required(true)
+                        .short("i")
+                        .takes_value(true)
+                        .value_name("FILE"),
+                )
+                .arg(
+                    Arg::with_name("output")
+                        .help("Output ConSert file, its extension selects the format (default: the other format on stdout)")
+                        .short("o")
+                        .takes_value(true)
+                        .value_name("FILE"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("validate")
+                .about("Validates ConSerts and reports all problems found")
+                .arg(
+                    Arg::with_name("input")
+                        .help("Input ConSert file")
+                        .required(true)
+                        .multiple(true)
+                        .short("i")
+                        .takes_value(true)
+                        .value_name("FILE"),
+                )
+                .arg(
+                    Arg::with_name("json")
+                        .help("Prints the reports as JSON")
+                        .long("json"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("eval")
+                .about("Evaluates a ConSert against a snapshot of runtime properties")
+                .arg(
+                    Arg::with_name("input")
+                        .help("Input ConSert file")
+                        .required(true)
+                        .short("i")
+                        .takes_value(true)
+                        .value_name("FILE"),
+                )
+                .arg(
+                    Arg::with_name("properties")
+                        .help("YAML or JSON file mapping evidence and demand ids to values, e.g. `E_Distance: 42 m`")
+                        .required(true)
+                        .short("p")
+                        .long("properties")
+                        .takes_value(true)
+                        .value_name("FILE"),
+                )
+                .arg(
+                    Arg::with_name("json")
+                        .help("Prints the evaluation as JSON")
+                        .long("json"),
+                ),
+        )
         .subcommand(
             SubCommand::with_name("compose")
-                .about("Composes ConSerts in the order they are provided")
+                .about("Composes ConSerts, regardless of the order they are provided in")
                 .arg(
                     Arg::with_name("input")
                         .help("input ConSert file")
@@ -88,6 +166,22 @@ pub fn get_cli_parameters() -> clap::ArgMatches<'static> {
                         .short("i")
                         .takes_value(true)
                         .value_name("FILE"),
+                )
+                .arg(
+                    Arg::with_name("relaxed")
+                        .help("Lets demands be fulfilled by guarantees of any service, regardless of its functional type")
+                        .long("relaxed"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("schema")
+                .about("Prints the JSON Schema of the YAML and JSON ConSert formats")
+                .arg(
+                    Arg::with_name("output")
+                        .help("Output file (default: stdout)")
+                        .short("o")
+                        .takes_value(true)
+                        .value_name("FILE"),
                 ),
         );
     app.get_matches()
@@ -95,14 +189,32 @@ pub fn get_cli_parameters() -> clap::ArgMatches<'static> {
 
 #[cfg(not(tarpaulin_include))] // integration function
 pub fn run(matches: &ArgMatches) -> Result<()> {
+    let mut quantities = Quantities::default();
+    for path in matches.values_of("units").into_iter().flatten() {
+        conserts_parse::quantities::load(&path, &mut quantities)
+            .map_err(|error| diagnostic::report(path, error))?;
+    }
+    quantities.scope(|| run_subcommand(matches))
+}
+
+#[cfg(not(tarpaulin_include))] // integration function
+fn run_subcommand(matches: &ArgMatches) -> Result<()> {
     if let Some(matches) = matches.subcommand_matches("parse") {
         parse(matches)?;
     } else if let Some(matches) = matches.subcommand_matches("plot") {
         plot(matches)?;
     } else if let Some(matches) = matches.subcommand_matches("compile") {
         compile(matches)?;
+    } else if let Some(matches) = matches.subcommand_matches("convert") {
+        convert(matches)?;
+    } else if let Some(matches) = matches.subcommand_matches("validate") {
+        validate(matches)?;
+    } else if let Some(matches) = matches.subcommand_matches("eval") {
+        eval(matches)?;
     } else if let Some(matches) = matches.subcommand_matches("compose") {
         compose(matches)?;
+    } else if let Some(matches) = matches.subcommand_matches("schema") {
+        schema(matches)?;
     } else {
         println!("{}", matches.usage());
     }
@@ -153,16 +265,184 @@ fn compile(args: &ArgMatches) -> Result<()> {
     compile::report_result(&consert, &args)
 }
 
+#[cfg(not(tarpaulin_include))] // IO function
+fn convert(matches: &ArgMatches) -> Result<()> {
+    let path = matches
+        .value_of("input")
+        .ok_or_else(|| anyhow!("No input provided"))?;
+    let consert = consert_from_path(path)?;
+
+    let output = matches.value_of("output");
+    let format = match output {
+        Some(output) => Format::from_path(&output),
+        None => Format::from_path(&path).map(|format| format.other()),
+    }
+    .ok_or_else(|| anyhow!("Only .xml /.model, .yml and .json supported"))?;
+
+    let conversion = conserts_parse::convert::convert(&consert, format)?;
+    for loss in conversion.losses.iter() {
+        eprintln!("{}: {}", "Warning".bright_yellow().bold(), loss);
+    }
+
+    match output {
+        Some(output) => {
+            std::fs::write(output, conversion.model)?;
+            println!(
+                "{}: Converted your ConSert to {}",
+                "Success".bright_green().bold(),
+                output.bold()
+            );
+        }
+        None => print!("{}", conversion.model),
+    }
+    Ok(())
+}
+
+#[cfg(not(tarpaulin_include))] // IO function
+fn schema(matches: &ArgMatches) -> Result<()> {
+    let schema = conserts_parse::json_schema()?;
+    match matches.value_of("output") {
+        Some(output) => std::fs::write(output, format!("{}\n", schema))?,
+        None => println!("{}", schema),
+    }
+    Ok(())
+}
+
+/// Exits with status 1 if any of the ConSerts is invalid.
+#[cfg(not(tarpaulin_include))] // IO function
+fn validate(matches: &ArgMatches) -> Result<()> {
+    let files = matches
+        .values_of("input")
+        .ok_or_else(|| anyhow!("Expected parameter"))?;
+    let reports = files
+        .map(|file| conserts_parse::validate::validate(&file))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if matches.is_present("json") {
+        println!("{}", serde_json::to_string_pretty(&reports)?);
+    } else {
+        for report in reports.iter() {
+            if report.valid {
+                println!(
+                    "{}: {} is valid",
+                    "Success".bright_green().bold(),
+                    report.path.bold()
+                );
+            }
+            for problem in report.problems.iter() {
+                let severity = match problem.severity {
+                    Severity::Error => "Error".bright_red().bold(),
+                    Severity::Warning => "Warning".bright_yellow().bold(),
+                };
+                eprintln!("{}: {}", severity, problem.message);
+                if let Some(location) = problem.location() {
+                    let model = std::fs::read_to_string(&report.path)?;
+                    eprintln!("{}", diagnostic::code_frame(&report.path, &model, location));
+                }
+            }
+        }
+    }
+
+    if reports.iter().any(|report| !report.valid) {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// The best guarantee is the first holding one of the model that no other holding guarantee
+/// dominates.
+#[cfg(not(tarpaulin_include))] // IO function
+fn eval(matches: &ArgMatches) -> Result<()> {
+    let path = matches
+        .value_of("input")
+        .ok_or_else(|| anyhow!("No input provided"))?;
+    let consert = consert_from_path(path)?;
+    let properties_path = matches
+        .value_of("properties")
+        .ok_or_else(|| anyhow!("No properties provided"))?;
+    let properties = conserts_parse::properties::load(&properties_path)?;
+    let evaluation = evaluate(&consert, &properties)?;
+    let best = evaluation.best(&consert);
+
+    if matches.is_present("json") {
+        let guarantees = evaluation
+            .guarantees
+            .iter()
+            .map(|(id, holds)| serde_json::json!({ "id": id, "holds": holds }))
+            .collect::<Vec<_>>();
+        let report = serde_json::json!({
+            "evidence": evaluation.evidence,
+            "demands": evaluation.demands,
+            "guarantees": guarantees,
+            "best": best,
+            "unknown": evaluation.unknown,
+        });
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    let status = |fulfilled: bool| match fulfilled {
+        true => "fulfilled".bright_green(),
+        false => "not fulfilled".bright_red(),
+    };
+    for (id, fulfilled) in evaluation.evidence.iter().chain(evaluation.demands.iter()) {
+        let unknown = match evaluation.unknown.contains(id) {
+            true => " (unknown)",
+            false => "",
+        };
+        println!("{} is {}{}", id.bold(), status(*fulfilled), unknown);
+    }
+    for (id, holds) in evaluation.guarantees.iter() {
+        let holds = match holds {
+            true => "holds".bright_green(),
+            false => "does not hold".bright_red(),
+        };
+        println!("{} {}", id.bold(), holds);
+    }
+    match best {
+        Some(best) => println!(
+            "{}: {} is the best guarantee",
+            "Success".bright_green().bold(),
+            best.bold()
+        ),
+        None => println!("{}: No guarantee holds", "Warning".bright_yellow().bold()),
+    }
+    Ok(())
+}
+
 #[cfg(not(tarpaulin_include))] // IO function
 fn compose(matches: &ArgMatches) -> Result<()> {
     let files = matches
         .values_of("input")
         .ok_or_else(|| anyhow!("Expected parameter"))?;
-    let mut sos = SystemOfSystems::new();
+    let conserts = files
+        .map(|file| consert_from_path(file).map(Rc::new))
+        .collect::<Result<Vec<_>>>()?;
 
-    for file in files {
-        let consert = Rc::new(consert_from_path(file)?);
-        sos.add_consert(consert)?;
+    let matching = match matches.is_present("relaxed") {
+        true => Matching::Relaxed,
+        false => Matching::ServiceType,
+    };
+    let (_, composition) = SystemOfSystems::new()
+        .matching(matching)
+        .compose(conserts)?;
+    for binding in composition.bindings.iter() {
+        println!(
+            "{}: Demand {} of {} is fulfilled by guarantee {} of {} (rank {})",
+            "Info".bright_blue().bold(),
+            binding.demand.bold(),
+            binding.consumer,
+            binding.guarantee.bold(),
+            binding.provider,
+            binding.rank + 1
+        );
+    }
+    for cycle in composition.cycles.iter() {
+        println!(
+            "{}: {} depend on each other",
+            "Info".bright_blue().bold(),
+            cycle.join(", ").bold()
+        );
     }
     println!("{}: Composition possible.", "Success".bright_green().bold(),);
     Ok(())
@@ -170,13 +450,9 @@ fn compose(matches: &ArgMatches) -> Result<()> {
 
 #[cfg(not(tarpaulin_include))] // IO function
 fn consert_from_path(path: &str) -> Result<Consert> {
-    use conserts_parse::{Xml, Yaml};
-    let consert = if path.ends_with(".xml") || path.ends_with(".model") {
-        Consert::from_path_xml::<&str>(&path)?
-    } else if path.ends_with(".yml") {
-        Consert::from_path_yaml::<&str>(&path)?
-    } else {
-        return Err(anyhow!("Only .xml /.model and .yml supported"));
-    };
-    Ok(consert)
+    let format = Format::from_path(&path)
+        .ok_or_else(|| anyhow!("Only .xml /.model, .yml and .json supported"))?;
+    format
+        .read(&path)
+        .map_err(|error| diagnostic::report(path, error))
 }
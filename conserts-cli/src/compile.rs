@@ -8,7 +8,9 @@ use color_eyre::{
 };
 use conserts_compile::compile::monitor::FilterConfiguration;
 use conserts_compile::compile::CompileParameters;
+use conserts_compose::compose::SystemOfSystems;
 use conserts_elements::consert::Consert;
+use std::rc::Rc;
 
 pub(crate) mod report;
 
@@ -43,8 +45,26 @@ pub(super) fn parse_args(args: &clap::ArgMatches) -> Result<CompileParameters> {
     ))
 }
 
+/// Links the demands of the ConSert to the guarantees of the providers, if there are any.
 #[cfg(not(tarpaulin_include))] // integration function
-pub(super) fn test_composition(_: &mut Consert, _: &CompileParameters) -> Result<()> {
+pub(super) fn test_composition(
+    consert: &mut Consert,
+    parameters: &CompileParameters,
+) -> Result<()> {
+    let providers = parameters.providers();
+    if providers.is_empty() {
+        return Ok(());
+    }
+
+    // only the ConSert is checked, the providers may have demands of their own
+    let providers = providers
+        .iter()
+        .map(|provider| super::consert_from_path(provider).map(Rc::new))
+        .collect::<Result<Vec<_>>>()?;
+    SystemOfSystems::new()
+        .providers(providers)
+        .link_consert(consert)?;
+    report::linked_guarantees(consert);
     Ok(())
 }
 
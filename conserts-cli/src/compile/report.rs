@@ -5,6 +5,7 @@
 use std::path::Path;
 
 use colored::*;
+use conserts_elements::consert::Consert;
 
 #[cfg(not(tarpaulin_include))] // IO function
 pub(super) fn general_result(base_path: &Path) {
@@ -22,3 +23,21 @@ pub(crate) fn rust_missing() {
         "Warning".bright_yellow().bold(),
     );
 }
+
+#[cfg(not(tarpaulin_include))] // IO function
+pub(super) fn linked_guarantees(consert: &Consert) {
+    #![allow(clippy::unwrap_used)]
+    for demand in consert.demands() {
+        let demand = demand.lock().unwrap();
+        for (rank, (crate_name, guarantee)) in demand.guarantees().iter().enumerate() {
+            println!(
+                "{}: Linked demand {} to guarantee {} of {} (rank {})",
+                "Info".bright_blue().bold(),
+                demand.id.bold(),
+                guarantee.id.bold(),
+                crate_name,
+                rank + 1
+            );
+        }
+    }
+}
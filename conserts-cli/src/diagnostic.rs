@@ -0,0 +1,63 @@
+// SPDX-FileCopyrightText: 2022 Fraunhofer Institute for Experimental Software Engineering IESE
+//
+// SPDX-License-Identifier: MIT
+
+use color_eyre::eyre::Report;
+use color_eyre::{Section, SectionExt};
+use colored::*;
+use conserts_elements::{demands::Demand, services::RequiredService};
+use conserts_error::{ConSertError, Location};
+
+/// Lines shown before the line of the error.
+const CONTEXT: usize = 2;
+
+/// Adds the part of the model the error refers to, if the error carries a location.
+#[cfg(not(tarpaulin_include))] // IO function
+pub(crate) fn report(path: &str, error: ConSertError<Demand, RequiredService>) -> Report {
+    let frame = match &error {
+        ConSertError::Parsing { source } => source.location().and_then(|location| {
+            let model = std::fs::read_to_string(path).ok()?;
+            Some(code_frame(path, &model, location))
+        }),
+        _ => None,
+    };
+    let report = Report::new(error);
+    match frame {
+        Some(frame) => report.section(frame.header("Source:")),
+        None => report,
+    }
+}
+
+pub(crate) fn code_frame(path: &str, model: &str, location: Location) -> String {
+    let lines: Vec<&str> = model.lines().collect();
+    let last = location.line.min(lines.len());
+    let first = last.saturating_sub(CONTEXT).max(1);
+    let width = last.to_string().len();
+    let gutter = |number: &str| format!("{:>width$} |", number, width = width).bright_blue();
+
+    let mut frame = vec![
+        format!(
+            "{}{} {}:{}:{}",
+            " ".repeat(width),
+            "-->".bright_blue(),
+            path,
+            location.line,
+            location.column
+        ),
+        gutter("").to_string(),
+    ];
+    for number in first..=last {
+        frame.push(format!(
+            "{} {}",
+            gutter(&number.to_string()),
+            lines[number - 1]
+        ));
+    }
+    frame.push(format!(
+        "{} {}{}",
+        gutter(""),
+        " ".repeat(location.column.saturating_sub(1)),
+        "^".bright_red().bold()
+    ));
+    frame.join("\n")
+}
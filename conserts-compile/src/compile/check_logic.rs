@@ -4,9 +4,12 @@
 
 use std::sync::Mutex;
 
-use super::render::{Render, RenderEvidence, RenderProperty};
+use super::render::{Render, RenderEvidence, RenderProperty, RenderUnit};
 use crate::compile::TokenStreamJoin;
 use conserts_elements::demands::Demand;
+use conserts_elements::dimension::OrdinalBound;
+use conserts_elements::interval_set::IntervalSet;
+use conserts_elements::numeric_range::NumericRange;
 use proc_macro2::TokenStream;
 
 pub(crate) trait CheckLogic: RenderEvidence {
@@ -39,6 +42,33 @@ impl CheckLogic for conserts_elements::elements::evidence::Evidence {
                     }
                 )
             }
+            conserts_elements::dimension::Dimension::Ordinal {
+                levels,
+                level,
+                bound,
+                ..
+            } => {
+                let t = self.type_identifier();
+                let field = self.field_identifier();
+                // the level is the index in the declared ordering
+                let level_check = match levels.iter().position(|l| l.eq(&level)) {
+                    Some(index) => match bound {
+                        OrdinalBound::AtLeast => quote!(*value >= #index),
+                        OrdinalBound::AtMost => quote!(*value <= #index),
+                    },
+                    None => quote!(false),
+                };
+                quote!(
+                    {
+                        use crate::properties::#t::*;
+                        #documentation
+                        match &runtime_properties.#field {
+                            Unknown => false,
+                            Known(value) => #level_check,
+                        }
+                    }
+                )
+            }
             conserts_elements::dimension::Dimension::Numeric {
                 r#type: _,
                 covered,
@@ -48,40 +78,55 @@ impl CheckLogic for conserts_elements::elements::evidence::Evidence {
                 let t = self.type_identifier();
                 let field = self.field_identifier();
 
-                match uom {
+                // the range is checked in the unit of the dimension, unitless values are plain
+                let value = match uom {
                     Some(uom) => {
-                        let quantity_ident = format_ident!("{}", uom.quantity());
-                        let measurement_unit_ident = format_ident!("{}", uom.measurement_unit());
-                        let range_check = covered
-                            .into_iter()
-                            .map(|r| {
-                            let tokens = r.render();
-                            quote!(
-                                #tokens.contains(&value.get::<uom::si::#quantity_ident::#measurement_unit_ident>())
-                            )
-                            }
-                            )
-                            .collect::<Vec<TokenStream>>()
-                            .join_with(quote!(||));
-                        quote!(
-                        {
-                            use crate::properties::#t::*;
-                            #documentation
-                            match &runtime_properties.#field {
-                                Unknown => false,
-                                Known(value) => {
-                                    #range_check
-                                }
-                            }
-                        })
+                        let unit_type = uom.unit_type();
+                        quote!(value.get::<#unit_type>())
                     }
-                    None => todo!(),
-                }
+                    None => quote!(*value),
+                };
+                let known = match range_check(&covered) {
+                    Some(range_check) => quote!(
+                        Known(value) => {
+                            let value = #value;
+                            #range_check
+                        }
+                    ),
+                    None => quote!(Known(_) => false),
+                };
+                quote!(
+                {
+                    use crate::properties::#t::*;
+                    #documentation
+                    match &runtime_properties.#field {
+                        Unknown => false,
+                        #known
+                    }
+                })
             }
         }
     }
 }
 
+/// Renders the check whether a `value: f64` in scope lies in one of the ranges,
+/// or `None` if the ranges cover no value at all.
+fn range_check(covered: &[NumericRange]) -> Option<TokenStream> {
+    let check = match covered.iter().collect::<IntervalSet>().intervals() {
+        [] => return None,
+        [interval] => interval.render(),
+        intervals => intervals
+            .iter()
+            .map(|interval| {
+                let check = interval.render();
+                quote!((#check))
+            })
+            .collect::<Vec<TokenStream>>()
+            .join_with(quote!(||)),
+    };
+    Some(check)
+}
+
 impl CheckLogic for Demand {
     fn check_logic(&self) -> proc_macro2::TokenStream {
         let t = self.type_identifier();
@@ -170,6 +215,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_check_logic_ordinal() {
+        let evidence = Evidence::new(
+            0,
+            "OrdinalEvidence",
+            None,
+            Dimension::Ordinal {
+                r#type: "Type".into(),
+                levels: vec!["Low".into(), "Medium".into(), "High".into()],
+                level: "Medium".into(),
+                bound: OrdinalBound::AtLeast,
+                subset: SubsetRelationship::Guarantee,
+            },
+        );
+        assert_eq!(
+            evidence.check_logic().to_string(),
+            quote!({
+                use crate::properties::OrdinalEvidence::*;
+                match &runtime_properties.ordinal_evidence {
+                    Unknown => false,
+                    Known(value) => *value >= 1usize,
+                }
+            })
+            .to_string()
+        );
+    }
+
     #[test]
     fn test_check_logic_numeric() {
         let evidence = Evidence::new(
@@ -193,14 +265,262 @@ mod tests {
                 match &runtime_properties.numeric_evidence {
                     Unknown => false,
                     Known(value) => {
-                        (0f64..=40f64).contains(&value.get::<uom::si::time::millisecond>())
-                            || (50f64..=60f64).contains(&value.get::<uom::si::time::millisecond>())
+                        let value = value.get::<uom::si::time::millisecond>();
+                        (value >= 0f64 && value <= 40f64) || (value >= 50f64 && value <= 60f64)
+                    }
+                }
+            })
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_check_logic_unit_paths() {
+        for (unit, path) in [
+            ("km/h", quote!(uom::si::velocity::kilometer_per_hour)),
+            (
+                "rpm",
+                quote!(uom::si::angular_velocity::revolution_per_minute),
+            ),
+            (
+                "°C",
+                quote!(uom::si::thermodynamic_temperature::degree_celsius),
+            ),
+            (
+                "m/s²",
+                quote!(uom::si::acceleration::meter_per_second_squared),
+            ),
+            ("mA", quote!(uom::si::electric_current::milliampere)),
+            ("%", quote!(uom::si::ratio::percent)),
+        ] {
+            let evidence = Evidence::new(
+                0,
+                "NumericEvidence",
+                None,
+                Dimension::Numeric {
+                    r#type: "Type".into(),
+                    covered: vec![NumericRange::Inclusive(RangeInclusive::new(0.0, 40.0))],
+                    subset: SubsetRelationship::Demand,
+                    uom: Some(uom::UnitOfMeasure::new(unit).unwrap()),
+                },
+            );
+            assert_eq!(
+                evidence.check_logic().to_string(),
+                quote!({
+                    use crate::properties::NumericEvidence::*;
+                    match &runtime_properties.numeric_evidence {
+                        Unknown => false,
+                        Known(value) => {
+                            let value = value.get::<#path>();
+                            value >= 0f64 && value <= 40f64
+                        }
+                    }
+                })
+                .to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn test_check_logic_custom_unit() {
+        let mut quantities = uom::Quantities::default();
+        quantities
+            .declare(uom::CustomQuantity {
+                name: "Illuminance".into(),
+                units: vec![uom::CustomUnit {
+                    name: "kilolux".into(),
+                    abbreviation: Some("klx".into()),
+                    plural: None,
+                    aliases: vec![],
+                    factor: 1000.0,
+                }],
+            })
+            .unwrap();
+        let evidence = Evidence::new(
+            0,
+            "NumericEvidence",
+            None,
+            Dimension::Numeric {
+                r#type: "Type".into(),
+                covered: vec![NumericRange::from(">", 5.0).unwrap()],
+                subset: SubsetRelationship::Demand,
+                uom: Some(quantities.scope(|| uom::UnitOfMeasure::new("klx").unwrap())),
+            },
+        );
+        assert_eq!(
+            evidence.check_logic().to_string(),
+            quote!({
+                use crate::properties::NumericEvidence::*;
+                match &runtime_properties.numeric_evidence {
+                    Unknown => false,
+                    Known(value) => {
+                        let value = value.get::<crate::properties::illuminance::kilolux>();
+                        value > 5f64
+                    }
+                }
+            })
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_check_logic_unitless() {
+        let evidence = Evidence::new(
+            0,
+            "Confidence",
+            None,
+            Dimension::Numeric {
+                r#type: "Confidence".into(),
+                covered: vec![NumericRange::from(">=", 0.9).unwrap()],
+                subset: SubsetRelationship::Demand,
+                uom: None,
+            },
+        );
+        assert_eq!(
+            evidence.check_logic().to_string(),
+            quote!({
+                use crate::properties::Confidence::*;
+                match &runtime_properties.confidence {
+                    Unknown => false,
+                    Known(value) => {
+                        let value = *value;
+                        value >= 0.9f64
+                    }
+                }
+            })
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_check_logic_merged_ranges() {
+        let evidence = Evidence::new(
+            0,
+            "NumericEvidence",
+            None,
+            Dimension::Numeric {
+                r#type: "Type".into(),
+                covered: vec![
+                    NumericRange::from(">", 50.0).unwrap(),
+                    NumericRange::Inclusive(RangeInclusive::new(0.0, 40.0)),
+                    NumericRange::Inclusive(RangeInclusive::new(20.0, 50.0)),
+                ],
+                subset: SubsetRelationship::Demand,
+                uom: Some(uom::UnitOfMeasure::new("ms").unwrap()),
+            },
+        );
+        assert_eq!(
+            evidence.check_logic().to_string(),
+            quote!({
+                use crate::properties::NumericEvidence::*;
+                match &runtime_properties.numeric_evidence {
+                    Unknown => false,
+                    Known(value) => {
+                        let value = value.get::<uom::si::time::millisecond>();
+                        value >= 0f64
                     }
                 }
             })
             .to_string()
         );
     }
+
+    /// Compiles the range checks with rustc and evaluates them for values around their bounds.
+    #[test]
+    fn test_range_check_execution() {
+        let values = [
+            f64::MIN,
+            -4.0,
+            -3.0,
+            -2.5,
+            0.0,
+            1.0,
+            2.0,
+            3.5,
+            5.0,
+            6.0,
+            f64::MAX,
+        ];
+        // whether the values above are covered
+        let ranges = vec![
+            (vec![NumericRange::from(">", 2.0).unwrap()], "00000001111"),
+            (vec![NumericRange::from(">=", 2.0).unwrap()], "00000011111"),
+            (vec![NumericRange::from("<", 2.0).unwrap()], "11111100000"),
+            (vec![NumericRange::from("<=", 2.0).unwrap()], "11111110000"),
+            (vec![NumericRange::from("<", -3.0).unwrap()], "11000000000"),
+            (vec![NumericRange::from(">", -3.0).unwrap()], "00011111111"),
+            (
+                vec![NumericRange::between(-3.0, false, 2.0, true)],
+                "00011110000",
+            ),
+            (
+                vec![NumericRange::between(-3.0, true, 2.0, false)],
+                "00111100000",
+            ),
+            (vec![NumericRange::Exclusive(-3.0..5.0)], "00011111000"),
+            (vec![NumericRange::Inclusive(5.0..=-3.0)], "00000000000"),
+            (
+                vec![
+                    NumericRange::Exclusive(-3.0..0.0),
+                    NumericRange::Inclusive(2.0..=5.0),
+                ],
+                "00010011100",
+            ),
+        ];
+
+        let functions = (0..ranges.len())
+            .map(|index| format_ident!("check_{}", index))
+            .collect::<Vec<_>>();
+        let checks = ranges
+            .iter()
+            .map(|(covered, _)| range_check(covered).unwrap_or_else(|| quote!(false)));
+        let calls = functions
+            .iter()
+            .map(|function| quote!(#( print!("{}", #function(#values) as u8); )*));
+        let program = quote!(
+            #(
+                fn #functions(value: f64) -> bool {
+                    let _ = value;
+                    #checks
+                }
+            )*
+            fn main() {
+                #(
+                    #calls
+                    println!();
+                )*
+            }
+        );
+        let program = format!("#![deny(warnings)]\n{}\n", program);
+
+        let directory = std::env::temp_dir().join(format!("conserts-range-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let source = directory.join("main.rs");
+        let binary = directory.join("main");
+        std::fs::write(&source, program).unwrap();
+        let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+        let compiled = std::process::Command::new(rustc)
+            .arg(&source)
+            .arg("-o")
+            .arg(&binary)
+            .output()
+            .unwrap();
+        assert!(
+            compiled.status.success(),
+            "{}",
+            String::from_utf8_lossy(&compiled.stderr)
+        );
+        let output = std::process::Command::new(&binary).output().unwrap();
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        let expected = ranges
+            .iter()
+            .map(|(_, expected)| *expected)
+            .collect::<Vec<_>>();
+        let actual = String::from_utf8(output.stdout).unwrap();
+        assert_eq!(actual.lines().collect::<Vec<_>>(), expected);
+    }
+
     #[test]
     fn test_check_logic_demand() {
         let evidence = Evidence::new(
@@ -28,7 +28,7 @@ pub(super) fn create_directories<T: AsRef<path::Path>>(
     base_path: T,
     _: &crate::compile::CompileParameters,
 ) -> Result<(), ConSertError<Demand, RequiredService>> {
-    fs::create_dir_all(&base_path.as_ref().join("src"))?;
+    fs::create_dir_all(base_path.as_ref().join("src"))?;
     Ok(())
 }
 
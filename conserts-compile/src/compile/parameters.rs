@@ -76,7 +76,7 @@ mod tests {
         );
         assert_eq!(
             cp.base_path(&consert),
-            std::path::PathBuf::new().join(&format!(
+            std::path::PathBuf::new().join(format!(
                 ".{0}target{0}consert_Test",
                 std::path::MAIN_SEPARATOR
             ))
@@ -86,7 +86,7 @@ mod tests {
             current_dir
                 .canonicalize()
                 .unwrap()
-                .join(&format!("target{}consert_Test", std::path::MAIN_SEPARATOR))
+                .join(format!("target{}consert_Test", std::path::MAIN_SEPARATOR))
         );
         assert_eq!(cp.path(), "FooBar".to_string());
         assert_eq!(
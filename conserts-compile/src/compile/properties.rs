@@ -4,7 +4,8 @@
 
 use super::render::RenderProperty;
 use crate::compile::TokenStreamJoin;
-use conserts_elements::consert::Consert;
+use conserts_elements::{consert::Consert, dimension::Dimension, uom::CustomQuantity};
+use inflector::Inflector;
 use proc_macro2::TokenStream;
 use std::sync::Arc;
 
@@ -21,10 +22,12 @@ pub(super) fn render(consert: &Consert) -> impl Iterator<Item = crate::compile::
         )
         .collect::<Vec<_>>();
 
+    let quantity_declarations = render_quantity_declarations(consert);
     let property_declarations = render_property_declarations(&properties);
     let property_field_declarations = render_property_field_declarations(&properties);
     let unknown_property_inits = render_unknown_property_inits(&properties);
     render_properties_module(
+        quantity_declarations,
         property_declarations,
         property_field_declarations,
         unknown_property_inits,
@@ -32,6 +35,7 @@ pub(super) fn render(consert: &Consert) -> impl Iterator<Item = crate::compile::
 }
 
 fn render_properties_module(
+    quantity_declarations: TokenStream,
     property_declarations: TokenStream,
     property_field_declarations: TokenStream,
     unknown_property_inits: TokenStream,
@@ -39,6 +43,8 @@ fn render_properties_module(
     std::iter::once((
         std::path::PathBuf::new().join("src/properties.rs"),
         quote!(
+            #quantity_declarations
+
             #property_declarations
 
             //#[repr(C)]
@@ -59,6 +65,74 @@ fn render_properties_module(
     ))
 }
 
+/// Custom quantities of the evidence become newtypes over their value in the base unit,
+/// used like the quantities of `uom`: `DetectionRate::new::<detection_per_frame>(0.5)`.
+fn render_quantity_declarations(consert: &Consert) -> TokenStream {
+    let mut quantities: Vec<&CustomQuantity> = vec![];
+    let evidence = consert.evidence();
+    for evidence in evidence.iter() {
+        if let Dimension::Numeric { uom: Some(uom), .. } = &evidence.dimension {
+            if let Some(quantity) = uom.custom_quantity() {
+                if quantities.iter().all(|q| q.name != quantity.name) {
+                    quantities.push(quantity);
+                }
+            }
+        }
+    }
+    quantities
+        .into_iter()
+        .map(render_quantity_declaration)
+        .collect::<Vec<_>>()
+        .join()
+}
+
+fn render_quantity_declaration(quantity: &CustomQuantity) -> TokenStream {
+    let module = format_ident!("{}", quantity.name.to_snake_case());
+    let quantity_ident = format_ident!("{}", quantity.name);
+    let units = quantity
+        .units
+        .iter()
+        .map(|unit| {
+            let unit_ident = format_ident!("{}", unit.name);
+            let factor = unit.factor;
+            quote!(
+                #[allow(non_camel_case_types)]
+                #[derive(Clone, Copy, Debug)]
+                pub struct #unit_ident;
+                impl Unit for #unit_ident {
+                    const FACTOR: f64 = #factor;
+                }
+            )
+        })
+        .collect::<Vec<_>>()
+        .join();
+    quote!(
+        pub mod #module {
+            pub trait Unit {
+                /// The value of one unit in the base unit of the quantity.
+                const FACTOR: f64;
+            }
+
+            #units
+
+            #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+            pub struct #quantity_ident {
+                base: f64,
+            }
+
+            impl #quantity_ident {
+                pub fn new<U: Unit>(value: f64) -> Self {
+                    Self { base: value * U::FACTOR }
+                }
+
+                pub fn get<U: Unit>(&self) -> f64 {
+                    self.base / U::FACTOR
+                }
+            }
+        }
+    )
+}
+
 fn render_property_declarations(properties: &[Arc<dyn RenderProperty>]) -> TokenStream {
     properties
         .iter()
@@ -112,9 +186,11 @@ mod tests {
     use conserts_elements::{
         consert::ConsertBuilder,
         consert_tree::{ConsertTreeElement, Tree},
-        dimension::Dimension,
+        dimension::SubsetRelationship,
         evidence::Evidence,
         guarantees::Guarantee,
+        numeric_range::NumericRange,
+        uom::UnitOfMeasure,
     };
     use pretty_assertions::assert_eq;
 
@@ -174,4 +250,94 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn test_render_custom_quantity() {
+        let mut quantities = conserts_elements::uom::Quantities::default();
+        quantities
+            .declare(CustomQuantity {
+                name: "DetectionRate".into(),
+                units: vec![conserts_elements::uom::CustomUnit {
+                    name: "detection_per_frame".into(),
+                    abbreviation: Some("det/frame".into()),
+                    plural: Some("detections per frame".into()),
+                    aliases: vec![],
+                    factor: 1.0,
+                }],
+            })
+            .unwrap();
+        let runtime_evidence = Arc::new(Evidence::new(
+            0,
+            "Detections",
+            None,
+            Dimension::Numeric {
+                r#type: "Detections".into(),
+                covered: vec![NumericRange::from(">", 0.5).unwrap()],
+                subset: SubsetRelationship::Demand,
+                uom: Some(quantities.scope(|| UnitOfMeasure::new("detections per frame").unwrap())),
+            },
+        ));
+        let consert = ConsertBuilder::new()
+            .name("Test")
+            .path("Foo")
+            .insert_runtime_evidence(runtime_evidence);
+
+        assert_eq!(
+            render(&consert.build().unwrap()).next().unwrap(),
+            (
+                std::path::PathBuf::new().join("src/properties.rs"),
+                quote!(
+                    pub mod detection_rate {
+                        pub trait Unit {
+                            /// The value of one unit in the base unit of the quantity.
+                            const FACTOR: f64;
+                        }
+
+                        #[allow(non_camel_case_types)]
+                        #[derive(Clone, Copy, Debug)]
+                        pub struct detection_per_frame;
+                        impl Unit for detection_per_frame {
+                            const FACTOR: f64 = 1f64;
+                        }
+
+                        #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+                        pub struct DetectionRate {
+                            base: f64,
+                        }
+
+                        impl DetectionRate {
+                            pub fn new<U: Unit>(value: f64) -> Self {
+                                Self { base: value * U::FACTOR }
+                            }
+
+                            pub fn get<U: Unit>(&self) -> f64 {
+                                self.base / U::FACTOR
+                            }
+                        }
+                    }
+
+                    #[derive(Clone, Copy, Debug, PartialEq)]
+                    pub enum Detections {
+                        Unknown,
+                        Known(crate::properties::detection_rate::DetectionRate),
+                    }
+                    impl Eq for Detections {}
+
+                    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+                    pub struct RuntimeProperties {
+                        pub detections: Detections,
+                    }
+
+                    impl RuntimeProperties {
+                        pub fn unknown() -> RuntimeProperties {
+                            RuntimeProperties {
+                                detections: Detections::Unknown,
+                            }
+                        }
+                    }
+                )
+                .to_string()
+            )
+        );
+    }
 }
@@ -4,10 +4,10 @@
 
 use conserts_elements::{
     demands::Demand, dimension::Dimension, evidence::Evidence, guarantees::Guarantee,
-    numeric_range::NumericRange,
+    interval_set::Interval, numeric_range::NumericRange, uom::UnitOfMeasure,
 };
 use proc_macro2::{Ident, TokenStream};
-use std::{ops::Range, sync::Mutex};
+use std::{ops::Bound, sync::Mutex};
 
 extern crate inflector;
 use inflector::Inflector;
@@ -16,19 +16,59 @@ pub(crate) trait Render {
     fn render(&self) -> proc_macro2::TokenStream;
 }
 
+/// Renders the check whether a `value: f64` in scope lies in the range.
 impl Render for NumericRange {
     fn render(&self) -> proc_macro2::TokenStream {
-        match &self {
-            NumericRange::Exclusive(Range { start, end }) => quote!(
-                (#start..#end)
-            ),
-            NumericRange::Inclusive(range) => {
-                let start = range.start();
-                let end = range.end();
-                quote!(
-                    (#start..=#end)
-                )
+        Interval::from(self).render()
+    }
+}
+
+/// Renders the check whether a `value: f64` in scope lies in the interval.
+impl Render for Interval {
+    fn render(&self) -> proc_macro2::TokenStream {
+        let lower = match self.lower {
+            Bound::Included(start) => Some(quote!(value >= #start)),
+            Bound::Excluded(start) => Some(quote!(value > #start)),
+            Bound::Unbounded => None,
+        };
+        let upper = match self.upper {
+            Bound::Included(end) => Some(quote!(value <= #end)),
+            Bound::Excluded(end) => Some(quote!(value < #end)),
+            Bound::Unbounded => None,
+        };
+        match (lower, upper) {
+            (Some(lower), Some(upper)) => quote!(#lower && #upper),
+            (Some(bound), None) | (None, Some(bound)) => bound,
+            (None, None) => quote!(true),
+        }
+    }
+}
+
+/// Paths of the types of a unit in the generated crate. Custom quantities are declared in
+/// the properties module, the others are taken from `uom`.
+pub(crate) trait RenderUnit {
+    fn quantity_type(&self) -> TokenStream;
+    fn unit_type(&self) -> TokenStream;
+}
+
+impl RenderUnit for UnitOfMeasure {
+    fn quantity_type(&self) -> TokenStream {
+        let quantity = format_ident!("{}", self.Quantity());
+        match self.custom_quantity() {
+            Some(_) => {
+                let module = format_ident!("{}", self.quantity());
+                quote!(crate::properties::#module::#quantity)
             }
+            None => quote!(uom::si::f64::#quantity),
+        }
+    }
+
+    fn unit_type(&self) -> TokenStream {
+        let module = format_ident!("{}", self.quantity());
+        let unit = format_ident!("{}", self.measurement_unit());
+        match self.custom_quantity() {
+            Some(_) => quote!(crate::properties::#module::#unit),
+            None => quote!(uom::si::#module::#unit),
         }
     }
 }
@@ -122,35 +162,49 @@ impl RenderProperty for Evidence {
                     }
                 )
             }
+            Dimension::Ordinal { levels, .. } => {
+                let property_ident = format_ident!("{}", self.id.replace(' ', "").to_pascal_case());
+                let levels_ident = format_ident!(
+                    "{}_LEVELS",
+                    self.id.replace(' ', "").to_screaming_snake_case()
+                );
+
+                quote!(
+                    #[derive(Eq)]
+                    pub enum #property_ident {
+                        Unknown,
+                        Known(usize)
+                    }
+                    const #levels_ident: &[&str] = &[#(#levels),*];
+                    impl #property_ident {
+                        pub fn from_str(value: &str) -> Self {
+                            match #levels_ident.iter().position(|level| *level == value) {
+                                Some(index) => Self::Known(index),
+                                None => Self::Unknown,
+                            }
+                        }
+                    }
+                )
+            }
             Dimension::Numeric {
                 r#type: _,
                 covered: _,
                 subset: _,
                 uom,
-            } => match uom {
-                Some(uom) => {
-                    let property_ident =
-                        format_ident!("{}", self.id.replace(' ', "").to_pascal_case());
-                    let quantity_ident = format_ident!("{}", uom.Quantity());
-                    quote!(
-                        pub enum #property_ident {
-                            Unknown,
-                            Known(uom::si::f64::#quantity_ident),
-                        }
-                        impl Eq for #property_ident { }
-                    )
-                }
-                None => {
-                    let property_ident =
-                        format_ident!("{}", self.id.replace(' ', "").to_pascal_case());
-                    quote!(
-                        pub enum #property_ident {
-                            Unknown,
-                            Known(bool),
-                        }
-                    )
-                }
-            },
+            } => {
+                let property_ident = format_ident!("{}", self.id.replace(' ', "").to_pascal_case());
+                let value_type = match uom {
+                    Some(uom) => uom.quantity_type(),
+                    None => quote!(f64),
+                };
+                quote!(
+                    pub enum #property_ident {
+                        Unknown,
+                        Known(#value_type),
+                    }
+                    impl Eq for #property_ident { }
+                )
+            }
         }
     }
 
@@ -168,12 +222,32 @@ impl RenderProperty for Evidence {
 impl RenderProperty for Demand {
     fn render_type_declaration(&self) -> TokenStream {
         let property_ident = format_ident!("{}", self.id.replace(' ', "").to_pascal_case());
+        let guarantees = match self.linked_guarantees.is_empty() {
+            true => quote!(),
+            false => {
+                let guarantees = self
+                    .linked_guarantees
+                    .iter()
+                    .map(|(crate_name, guarantee)| {
+                        let id = &guarantee.id;
+                        quote!((#crate_name, #id))
+                    })
+                    .collect::<Vec<_>>();
+                quote!(
+                    impl #property_ident {
+                        /// The crates and guarantees fulfilling the demand, from the strongest to the weakest.
+                        pub const GUARANTEES: &'static [(&'static str, &'static str)] = &[#(#guarantees),*];
+                    }
+                )
+            }
+        };
         quote!(
             #[derive(Eq)]
             pub enum #property_ident {
                 Unknown,
                 Known(bool),
             }
+            #guarantees
         )
     }
 
@@ -216,9 +290,33 @@ mod tests {
     #[test]
     fn test_numeric_range() {
         let r = NumericRange::Inclusive(0.0..=5.7);
-        assert_eq!(r.render().to_string(), quote!((0f64..=5.7f64)).to_string());
+        assert_eq!(
+            r.render().to_string(),
+            quote!(value >= 0f64 && value <= 5.7f64).to_string()
+        );
         let r = NumericRange::Exclusive(0.0..5.7);
-        assert_eq!(r.render().to_string(), quote!((0f64..5.7f64)).to_string());
+        assert_eq!(
+            r.render().to_string(),
+            quote!(value > 0f64 && value < 5.7f64).to_string()
+        );
+        // thresholds are exclusive, the unbounded end is not checked
+        let r = NumericRange::from(">", 5.7).unwrap();
+        assert_eq!(r.render().to_string(), quote!(value > 5.7f64).to_string());
+        let r = NumericRange::from("<=", -5.7).unwrap();
+        assert_eq!(r.render().to_string(), quote!(value <= -5.7f64).to_string());
+    }
+
+    #[test]
+    fn test_interval() {
+        let i = Interval::new(Bound::Included(0.0), Bound::Excluded(5.7));
+        assert_eq!(
+            i.render().to_string(),
+            quote!(value >= 0f64 && value < 5.7f64).to_string()
+        );
+        let i = Interval::new(Bound::Excluded(5.7), Bound::Unbounded);
+        assert_eq!(i.render().to_string(), quote!(value > 5.7f64).to_string());
+        let i = Interval::new(Bound::Unbounded, Bound::Unbounded);
+        assert_eq!(i.render().to_string(), quote!(true).to_string());
     }
 
     #[test]
@@ -307,6 +405,53 @@ mod tests {
             )
             .to_string()
         );
+        let runtime_evidence_numeric = Evidence::new(
+            0,
+            "FooBar",
+            None,
+            Dimension::Numeric {
+                r#type: "Rotation".into(),
+                covered: vec![],
+                subset: SubsetRelationship::Demand,
+                uom: Some(UnitOfMeasure::new("rad/s").unwrap()),
+            },
+        );
+        let declaration = runtime_evidence_numeric.render_type_declaration();
+        assert_eq!(
+            declaration.to_string(),
+            quote!(
+                pub enum FooBar {
+                    Unknown,
+                    Known(uom::si::f64::AngularVelocity),
+                }
+                impl Eq for FooBar {}
+            )
+            .to_string()
+        );
+        let runtime_evidence_unitless = Evidence::new(
+            0,
+            "Count",
+            None,
+            Dimension::Numeric {
+                r#type: "Count".into(),
+                covered: vec![],
+                subset: SubsetRelationship::Demand,
+                uom: None,
+            },
+        );
+        assert_eq!(
+            runtime_evidence_unitless
+                .render_type_declaration()
+                .to_string(),
+            quote!(
+                pub enum Count {
+                    Unknown,
+                    Known(f64),
+                }
+                impl Eq for Count {}
+            )
+            .to_string()
+        );
 
         let demand = Demand::new(
             "D0",
@@ -328,4 +473,64 @@ mod tests {
             .to_string()
         )
     }
+
+    #[test]
+    fn test_demand_render_guarantees() {
+        use conserts_elements::consert_tree::{ConsertTreeElement, Tree};
+        use conserts_elements::guarantees::Guarantee;
+        use std::sync::Arc;
+
+        let degraded = Guarantee::new(
+            0,
+            "Degraded",
+            None,
+            Dimension::Numeric {
+                r#type: "Latency".into(),
+                covered: vec![NumericRange::Inclusive(0.0..=100.0)],
+                subset: SubsetRelationship::Guarantee,
+                uom: Some(UnitOfMeasure::new("ms").unwrap()),
+            },
+            Tree::leaf(ConsertTreeElement::Tautology),
+        );
+        let full = Guarantee::new(
+            0,
+            "Full",
+            None,
+            Dimension::Numeric {
+                r#type: "Latency".into(),
+                covered: vec![NumericRange::Inclusive(0.0..=10.0)],
+                subset: SubsetRelationship::Guarantee,
+                uom: Some(UnitOfMeasure::new("ms").unwrap()),
+            },
+            Tree::leaf(ConsertTreeElement::Tautology),
+        );
+        let mut demand = Demand::new(
+            "D0",
+            None,
+            Dimension::Numeric {
+                r#type: "Latency".into(),
+                covered: vec![NumericRange::Inclusive(0.0..=200.0)],
+                subset: SubsetRelationship::Guarantee,
+                uom: Some(UnitOfMeasure::new("ms").unwrap()),
+            },
+        );
+        demand.link("provider".into(), Arc::new(degraded));
+        demand.link("provider".into(), Arc::new(full));
+        assert_eq!(
+            demand.render_type_declaration().to_string(),
+            quote!(
+                #[derive(Eq)]
+                pub enum D0 {
+                    Unknown,
+                    Known(bool),
+                }
+                impl D0 {
+                    /// The crates and guarantees fulfilling the demand, from the strongest to the weakest.
+                    pub const GUARANTEES: &'static [(&'static str, &'static str)] =
+                        &[("provider", "Full"), ("provider", "Degraded")];
+                }
+            )
+            .to_string()
+        )
+    }
 }
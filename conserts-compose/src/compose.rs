@@ -3,43 +3,109 @@
 // SPDX-License-Identifier: MIT
 
 use conserts_elements::consert::Consert;
+use conserts_elements::elements::guarantees::{rank, Guarantee, Mismatch};
 use conserts_elements::elements::{demands::Demand, services::RequiredService};
 use conserts_error::{CompositionError, ConSertError};
+use std::collections::{BTreeMap, BTreeSet};
 use std::rc::Rc;
+use std::sync::{Arc, Mutex};
 
 // Extension trait for Consert
 pub trait Link {
     fn link(&mut self, sos: &SystemOfSystems);
 }
 
-// TODO: write test for this function
 impl Link for Consert {
     fn link(&mut self, sos: &SystemOfSystems) {
         #![allow(clippy::unwrap_used)]
-        for existing_consert in sos.conserts().iter().filter(|c| c.path() != self.path()) {
-            for required_service in self.required_services().iter() {
-                for provided_service in existing_consert.provided_services().iter() {
-                    if required_service.matches_service_type(provided_service) {
-                        for guarantee in provided_service.guarantees() {
-                            for demand in required_service.demands().iter_mut() {
-                                if guarantee.fulfills(demand) {
-                                    demand
-                                        .lock()
-                                        .unwrap()
-                                        .link(existing_consert.crate_name(), guarantee.clone())
-                                }
-                            }
-                        }
-                    }
+        // the candidates follow the matching of the system, as in composition
+        for demand in self.demands().iter() {
+            for (existing_consert, guarantee) in sos.candidates(self, demand) {
+                if guarantee.fulfills(demand) {
+                    demand
+                        .lock()
+                        .unwrap()
+                        .link(existing_consert.crate_name(), guarantee)
                 }
             }
         }
     }
 }
 
+/// A demand of a ConSert bound to a guarantee of another ConSert that fulfills it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Binding {
+    pub consumer: String,
+    pub demand: String,
+    pub provider: String,
+    pub guarantee: String,
+    /// The position of the guarantee among those fulfilling the demand, from the strongest.
+    pub rank: usize,
+}
+
+/// The result of composing ConSerts as a whole.
+#[derive(Clone, Debug, Default)]
+pub struct Composition {
+    pub bindings: Vec<Binding>,
+    /// Groups of ConSerts that depend on each other, by name.
+    pub cycles: Vec<Vec<String>>,
+}
+
+/// The groups of ConSerts that reach each other through their bindings.
+fn cycles(bindings: &[Binding]) -> Vec<Vec<String>> {
+    let mut providers: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    for binding in bindings {
+        let _ = providers
+            .entry(&binding.consumer)
+            .or_default()
+            .insert(&binding.provider);
+    }
+    let reachable = |start: &str| {
+        let mut reached = BTreeSet::new();
+        let mut pending = vec![start];
+        while let Some(consert) = pending.pop() {
+            for provider in providers.get(consert).into_iter().flatten() {
+                if reached.insert(*provider) {
+                    pending.push(provider);
+                }
+            }
+        }
+        reached
+    };
+    let reachable: BTreeMap<&str, BTreeSet<&str>> = providers
+        .keys()
+        .map(|consert| (*consert, reachable(consert)))
+        .collect();
+
+    let mut cycles = BTreeSet::new();
+    for (consert, reached) in reachable.iter() {
+        if reached.contains(consert) {
+            let cycle = reached
+                .iter()
+                .filter(|other| reachable[**other].contains(consert))
+                .map(|other| other.to_string())
+                .collect::<Vec<_>>();
+            let _ = cycles.insert(cycle);
+        }
+    }
+    cycles.into_iter().collect()
+}
+
+/// Which guarantees of other ConSerts may fulfill a demand.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Matching {
+    /// Guarantees of provided services whose functional type matches the required service
+    /// owning the demand, as they are linked.
+    #[default]
+    ServiceType,
+    /// Any guarantee, regardless of the services.
+    Relaxed,
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct SystemOfSystems {
     conserts: Vec<Rc<Consert>>,
+    matching: Matching,
 }
 
 impl SystemOfSystems {
@@ -47,10 +113,26 @@ impl SystemOfSystems {
         SystemOfSystems::default()
     }
 
+    pub fn matching(mut self, matching: Matching) -> Self {
+        self.matching = matching;
+        self
+    }
+
+    #[allow(dead_code)]
     pub(crate) fn conserts(&self) -> Vec<Rc<Consert>> {
         self.conserts.clone()
     }
 
+    /// Adds ConSerts without checking that they can be composed, so that another ConSert can
+    /// be linked to their guarantees although their own demands are not fulfilled.
+    pub fn providers<I>(mut self, conserts: I) -> Self
+    where
+        I: IntoIterator<Item = Rc<Consert>>,
+    {
+        self.conserts.extend(conserts);
+        self
+    }
+
     #[allow(dead_code)]
     pub fn from_consert(
         consert: Rc<Consert>,
@@ -58,6 +140,7 @@ impl SystemOfSystems {
         if consert.is_independent() {
             Ok(SystemOfSystems {
                 conserts: vec![consert],
+                ..SystemOfSystems::default()
             })
         } else {
             Err(CompositionError::Dependent.into())
@@ -66,40 +149,215 @@ impl SystemOfSystems {
 
     pub(crate) fn can_consert_be_added(
         &self,
-        other: &Rc<Consert>,
+        other: &Consert,
     ) -> Result<(), ConSertError<Demand, RequiredService>> {
-        #![allow(clippy::unwrap_used)]
+        let (unmatched_demands, unmatched_required_services) = self.unmatched(other);
+        let composable = unmatched_demands.is_empty() && unmatched_required_services.is_empty();
+        if composable {
+            Ok(())
+        } else {
+            Err(self
+                .incompatible(other, unmatched_demands, unmatched_required_services)
+                .into())
+        }
+    }
+
+    /// The demands and required services of the ConSert that no other ConSert of the system
+    /// matches.
+    fn unmatched(&self, other: &Consert) -> (Vec<Arc<Mutex<Demand>>>, Vec<Arc<RequiredService>>) {
         let mut unmatched_demands = other.demands();
         let mut unmatched_required_services = other.required_services();
 
-        for existing_consert in self.conserts.iter() {
-            for guarantee in existing_consert.guarantees() {
-                // Retain those demands that are not fulfilled
-                unmatched_demands = unmatched_demands
-                    .into_iter()
-                    .filter(|demand| !guarantee.fulfills(demand))
-                    .collect();
-            }
+        // Retain those demands that are not fulfilled
+        unmatched_demands.retain(|demand| {
+            !self
+                .candidates(other, demand)
+                .iter()
+                .any(|(_, guarantee)| guarantee.fulfills(demand))
+        });
 
+        for existing_consert in self.conserts.iter().filter(|c| c.path() != other.path()) {
             for provided_service in existing_consert.provided_services().iter() {
                 // Retain those services that are not fulfilled
-                unmatched_required_services = unmatched_required_services
+                unmatched_required_services
+                    .retain(|service| !service.matches_service_type(provided_service));
+            }
+        }
+        (unmatched_demands, unmatched_required_services)
+    }
+
+    /// The required services of the ConSert the demand belongs to.
+    fn owners(other: &Consert, demand: &Arc<Mutex<Demand>>) -> Vec<Arc<RequiredService>> {
+        other
+            .required_services()
+            .into_iter()
+            .filter(|service| service.demands.iter().any(|d| Arc::ptr_eq(d, demand)))
+            .collect()
+    }
+
+    /// The guarantees of other ConSerts that may fulfill the demand according to the
+    /// matching, with the ConSert they belong to.
+    fn candidates(
+        &self,
+        other: &Consert,
+        demand: &Arc<Mutex<Demand>>,
+    ) -> Vec<(Rc<Consert>, Arc<Guarantee>)> {
+        let owners = Self::owners(other, demand);
+        let mut candidates = vec![];
+        for existing_consert in self.conserts.iter().filter(|c| c.path() != other.path()) {
+            let guarantees = match self.matching {
+                Matching::Relaxed => existing_consert.guarantees(),
+                Matching::ServiceType => existing_consert
+                    .provided_services()
+                    .iter()
+                    .filter(|provided| owners.iter().any(|s| s.matches_service_type(provided)))
+                    .flat_map(|provided| provided.guarantees())
+                    .collect(),
+            };
+            for guarantee in guarantees {
+                candidates.push((existing_consert.clone(), guarantee));
+            }
+        }
+        candidates
+    }
+
+    /// The error for the unmatched demands and required services of the ConSert, explaining
+    /// for every candidate guarantee why it does not fulfill the demand.
+    fn incompatible(
+        &self,
+        other: &Consert,
+        unmatched_demands: Vec<Arc<Mutex<Demand>>>,
+        unmatched_required_services: Vec<Arc<RequiredService>>,
+    ) -> CompositionError<Demand, RequiredService> {
+        #![allow(clippy::unwrap_used)]
+        let mut lines = vec![];
+        for demand in unmatched_demands.iter() {
+            let candidates = self.candidates(other, demand);
+            let owners = Self::owners(other, demand);
+            let demand = demand.lock().unwrap();
+            lines.push(match &demand.description {
+                Some(description) => {
+                    format!("- demand {} ({}) is unmatched", demand.id, description)
+                }
+                None => format!("- demand {} is unmatched", demand.id),
+            });
+            if candidates.is_empty() {
+                lines.push(match (self.matching, owners.is_empty()) {
+                    (Matching::ServiceType, true) => {
+                        "  - it belongs to no required service".to_string()
+                    }
+                    (Matching::ServiceType, false) => format!(
+                        "  - no ConSert provides a service of type {}",
+                        owners
+                            .iter()
+                            .map(|s| s.functional_service_type.as_str())
+                            .collect::<Vec<_>>()
+                            .join(" or ")
+                    ),
+                    (Matching::Relaxed, _) => {
+                        "  - no ConSert provides a guarantee for it".to_string()
+                    }
+                });
+            }
+            for (consert, guarantee) in candidates {
+                let mismatches = guarantee
+                    .mismatches(&demand)
+                    .iter()
+                    .map(Mismatch::to_string)
+                    .collect::<Vec<_>>();
+                let reason = match mismatches.is_empty() {
+                    true => "fulfills it, but is not linked".to_string(),
+                    false => mismatches.join("; "),
+                };
+                lines.push(format!(
+                    "  - guarantee {} of {}: {}",
+                    guarantee.id,
+                    consert.name(),
+                    reason
+                ));
+            }
+        }
+        for service in unmatched_required_services.iter() {
+            lines.push(format!(
+                "- required service {} is unmatched: no ConSert provides a service of type {}",
+                service.ident, service.functional_service_type
+            ));
+        }
+
+        CompositionError::Incompatible {
+            path: other.path(),
+            explanation: lines.join("\n"),
+            unmatched_demands,
+            unmatched_required_services,
+        }
+    }
+
+    /// Composes the ConSerts as a whole, so that their order does not matter and they can
+    /// depend on each other. Fails with the first ConSert whose demands or required services
+    /// the others do not match.
+    pub fn compose<I>(
+        mut self,
+        conserts: I,
+    ) -> Result<(SystemOfSystems, Composition), ConSertError<Demand, RequiredService>>
+    where
+        I: IntoIterator<Item = Rc<Consert>>,
+    {
+        self.conserts.extend(conserts);
+        let sos = self;
+        for consert in sos.conserts.iter() {
+            sos.can_consert_be_added(consert)?;
+        }
+
+        let bindings = sos.bindings();
+        let cycles = cycles(&bindings);
+        Ok((sos, Composition { bindings, cycles }))
+    }
+
+    fn bindings(&self) -> Vec<Binding> {
+        #![allow(clippy::unwrap_used)]
+        let mut bindings = vec![];
+        for consumer in self.conserts.iter() {
+            for demand in consumer.demands() {
+                let demand_id = demand.lock().unwrap().id.clone();
+                let mut fulfilling = self
+                    .candidates(consumer, &demand)
                     .into_iter()
-                    .filter(|service| !service.matches_service_type(provided_service))
-                    .collect();
+                    .filter(|(_, guarantee)| guarantee.fulfills(&demand))
+                    .collect::<Vec<_>>();
+                rank(&mut fulfilling, |(_, guarantee)| guarantee);
+                for (rank, (provider, guarantee)) in fulfilling.into_iter().enumerate() {
+                    bindings.push(Binding {
+                        consumer: consumer.name(),
+                        demand: demand_id.clone(),
+                        provider: provider.name(),
+                        guarantee: guarantee.id.clone(),
+                        rank,
+                    });
+                }
             }
         }
+        bindings
+    }
 
-        let composable = unmatched_demands.is_empty() && unmatched_required_services.is_empty();
-        if composable {
+    /// Links the demands of the ConSert to the guarantees of the system that fulfill them,
+    /// failing if the ConSert cannot be composed with the system or a demand remains unlinked.
+    pub fn link_consert(
+        &self,
+        consert: &mut Consert,
+    ) -> Result<(), ConSertError<Demand, RequiredService>> {
+        #![allow(clippy::unwrap_used)]
+        self.can_consert_be_added(consert)?;
+        consert.link(self);
+
+        let unmatched_demands = consert
+            .demands()
+            .into_iter()
+            .filter(|demand| demand.lock().unwrap().linked_guarantees.is_empty())
+            .collect::<Vec<_>>();
+        if unmatched_demands.is_empty() {
             Ok(())
         } else {
-            Err(CompositionError::Incompatible {
-                path: other.path(),
-                unmatched_demands,
-                unmatched_required_services,
-            }
-            .into())
+            Err(self.incompatible(consert, unmatched_demands, vec![]).into())
         }
     }
 
@@ -126,8 +384,11 @@ mod tests {
         consert_tree::{ConsertTreeElement, Tree},
         dimension::{Dimension, SubsetRelationship},
         guarantees::Guarantee,
+        numeric_range::NumericRange,
+        services::ProvidedService,
+        uom::UnitOfMeasure,
     };
-    use conserts_parse::Xml;
+    use conserts_parse::{Xml, Yaml};
 
     fn _generate_conserts() -> (Rc<Consert>, Rc<Consert>) {
         let leader =
@@ -192,17 +453,470 @@ mod tests {
             if let ConSertError::Composition {
                 source:
                     CompositionError::Incompatible {
-                        unmatched_demands, ..
+                        unmatched_demands,
+                        explanation,
+                        ..
                     },
             } = e
             {
                 assert!(unmatched_demands.len() == 1);
+                assert!(explanation.starts_with(
+                    "- demand SD1 (Speed Deviation is bound to <= 2 km/h) is unmatched\n"
+                ));
+                assert!(explanation.contains(
+                    "  - guarantee SG4 of deis_demoleadertrucksystemincompatible: \
+                     SpeedDeviationIsBoundTo (-∞, 5] km/h is not inside (-∞, 2] km/h"
+                ));
             } else {
                 panic!()
             }
         }
     }
 
+    #[test]
+    fn test_link_consert() {
+        let (leader, follower) = _generate_conserts();
+        let sos = SystemOfSystems::from_consert(leader).unwrap();
+        let mut follower = (*follower).clone();
+
+        sos.link_consert(&mut follower).unwrap();
+        for demand in follower.demands() {
+            let demand = demand.lock().unwrap();
+            assert!(
+                !demand.linked_guarantees.is_empty(),
+                "{} should be linked",
+                demand.id
+            );
+            assert!(demand
+                .linked_guarantees
+                .iter()
+                .all(|(crate_name, _)| crate_name == "consert_deis_demoleadertrucksystem"));
+        }
+    }
+
+    #[test]
+    fn test_link_consert_fail() {
+        let (_, follower) = _generate_conserts();
+        let leader = Rc::new(
+            Consert::from_path_xml(&"../models/DEIS_DemoLeaderTruckSystemIncompatible.model")
+                .unwrap(),
+        );
+        let sos = SystemOfSystems::from_consert(leader).unwrap();
+        let mut follower = (*follower).clone();
+
+        assert!(matches!(
+            sos.link_consert(&mut follower),
+            Err(ConSertError::Composition {
+                source: CompositionError::Incompatible { .. }
+            })
+        ));
+    }
+
+    #[test]
+    fn test_compose_order_independent() {
+        let (leader, follower) = _generate_conserts();
+
+        let mut sos = SystemOfSystems::new();
+        assert!(sos.add_consert(follower.clone()).is_err());
+
+        let (sos, composition) = SystemOfSystems::new()
+            .compose(vec![follower, leader])
+            .unwrap();
+        assert_eq!(sos.conserts().len(), 2);
+        assert!(!composition.bindings.is_empty());
+        assert!(composition.bindings.iter().all(|binding| {
+            binding.consumer == "deis_demofollowertrucksystem"
+                && binding.provider == "deis_demoleadertrucksystem"
+        }));
+        assert!(composition.cycles.is_empty());
+    }
+
+    #[test]
+    fn test_compose_mutual_dependency() {
+        let a = Rc::new(
+            Consert::from_yaml(
+                &"a.yml",
+                String::new(),
+                r#"
+guarantees:
+  - id: G_X
+    dimensions: [Categorical: {type: Exchange, covered: [X], subset: Demand}]
+evidence: []
+demands:
+  - id: D_Y
+    dimensions: [Categorical: {type: Exchange, covered: [Y], subset: Demand}]
+gates: []
+tree_propagations: [{from: D_Y, to: G_X}]
+required_services: [{id: Required, functional_service_type: Y, demands: [D_Y]}]
+provided_services: [{id: Provided, functional_service_type: X, guarantees: [G_X]}]
+"#
+                .into(),
+            )
+            .unwrap(),
+        );
+        let b = Rc::new(
+            Consert::from_yaml(
+                &"b.yml",
+                String::new(),
+                r#"
+guarantees:
+  - id: G_Y
+    dimensions: [Categorical: {type: Exchange, covered: [Y], subset: Demand}]
+evidence: []
+demands:
+  - id: D_X
+    dimensions: [Categorical: {type: Exchange, covered: [X], subset: Demand}]
+gates: []
+tree_propagations: [{from: D_X, to: G_Y}]
+required_services: [{id: Required, functional_service_type: X, demands: [D_X]}]
+provided_services: [{id: Provided, functional_service_type: Y, guarantees: [G_Y]}]
+"#
+                .into(),
+            )
+            .unwrap(),
+        );
+        let c = Rc::new(
+            Consert::from_yaml(
+                &"c.yml",
+                String::new(),
+                r#"
+guarantees:
+  - id: G_Z
+    dimensions: [Categorical: {type: Exchange, covered: [Z], subset: Demand}]
+evidence: []
+demands:
+  - id: D_X
+    dimensions: [Categorical: {type: Exchange, covered: [X], subset: Demand}]
+gates: []
+tree_propagations: [{from: D_X, to: G_Z}]
+required_services: [{id: Required, functional_service_type: X, demands: [D_X]}]
+provided_services: [{id: Provided, functional_service_type: Z, guarantees: [G_Z]}]
+"#
+                .into(),
+            )
+            .unwrap(),
+        );
+
+        let (_, composition) = SystemOfSystems::new().compose(vec![c, a, b]).unwrap();
+        let bindings = composition
+            .bindings
+            .iter()
+            .map(|b| {
+                (
+                    b.consumer.as_str(),
+                    b.demand.as_str(),
+                    b.provider.as_str(),
+                    b.guarantee.as_str(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            bindings,
+            vec![
+                ("c", "D_X", "a", "G_X"),
+                ("a", "D_Y", "b", "G_Y"),
+                ("b", "D_X", "a", "G_X"),
+            ]
+        );
+        assert_eq!(
+            composition.cycles,
+            vec![vec!["a".to_string(), "b".to_string()]]
+        );
+    }
+
+    #[test]
+    fn test_compose_rank() {
+        let degraded = Arc::new(Guarantee::new(
+            0,
+            "G_Degraded",
+            None,
+            Dimension::Numeric {
+                r#type: "Latency".into(),
+                covered: vec![NumericRange::Inclusive(0.0..=100.0)],
+                subset: SubsetRelationship::Guarantee,
+                uom: Some(UnitOfMeasure::new("ms").unwrap()),
+            },
+            Tree::leaf(ConsertTreeElement::Tautology),
+        ));
+        let full = Arc::new(Guarantee::new(
+            0,
+            "G_Full",
+            None,
+            Dimension::Numeric {
+                r#type: "Latency".into(),
+                covered: vec![NumericRange::Inclusive(0.0..=10.0)],
+                subset: SubsetRelationship::Guarantee,
+                uom: Some(UnitOfMeasure::new("ms").unwrap()),
+            },
+            Tree::leaf(ConsertTreeElement::Tautology),
+        ));
+        let provider = Rc::new(Consert::new(
+            "provider".into(),
+            "provider".into(),
+            String::new(),
+            vec![degraded.clone(), full.clone()],
+            vec![],
+            vec![Rc::new(ProvidedService::new(
+                "Provided",
+                vec![degraded, full],
+                "Latency",
+            ))],
+            vec![],
+            vec![],
+        ));
+        let demand = Arc::new(Mutex::new(Demand::new(
+            "D_Latency",
+            None,
+            Dimension::Numeric {
+                r#type: "Latency".into(),
+                covered: vec![NumericRange::Inclusive(0.0..=200.0)],
+                subset: SubsetRelationship::Guarantee,
+                uom: Some(UnitOfMeasure::new("ms").unwrap()),
+            },
+        )));
+        let mut consumer = Consert::new(
+            "consumer".into(),
+            "consumer".into(),
+            String::new(),
+            vec![],
+            vec![demand.clone()],
+            vec![],
+            vec![Arc::new(RequiredService::new(
+                "Required",
+                vec![demand.clone()],
+                "Latency",
+            ))],
+            vec![],
+        );
+
+        let (sos, composition) = SystemOfSystems::new()
+            .compose(vec![provider, Rc::new(consumer.clone())])
+            .unwrap();
+        let bindings = composition
+            .bindings
+            .iter()
+            .map(|b| (b.guarantee.as_str(), b.rank))
+            .collect::<Vec<_>>();
+        assert_eq!(bindings, vec![("G_Full", 0), ("G_Degraded", 1)]);
+
+        sos.link_consert(&mut consumer).unwrap();
+        let linked = demand
+            .lock()
+            .unwrap()
+            .guarantees()
+            .iter()
+            .map(|(_, guarantee)| guarantee.id.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(linked, vec!["G_Full", "G_Degraded"]);
+    }
+
+    #[test]
+    fn test_compose_fail() {
+        // b only relies on a, which cannot be composed
+        let a = Rc::new(
+            Consert::from_yaml(
+                &"a.yml",
+                String::new(),
+                r#"
+guarantees:
+  - id: G_X
+    dimensions: [Categorical: {type: Exchange, covered: [X], subset: Demand}]
+evidence: []
+demands:
+  - id: D_Y
+    dimensions: [Categorical: {type: Exchange, covered: [Y], subset: Demand}]
+gates: []
+tree_propagations: [{from: D_Y, to: G_X}]
+required_services: [{id: Required, functional_service_type: Y, demands: [D_Y]}]
+provided_services: [{id: Provided, functional_service_type: X, guarantees: [G_X]}]
+"#
+                .into(),
+            )
+            .unwrap(),
+        );
+        let b = Rc::new(
+            Consert::from_yaml(
+                &"b.yml",
+                String::new(),
+                r#"
+guarantees:
+  - id: G_Z
+    dimensions: [Categorical: {type: Exchange, covered: [Z], subset: Demand}]
+evidence: []
+demands:
+  - id: D_X
+    dimensions: [Categorical: {type: Exchange, covered: [X], subset: Demand}]
+gates: []
+tree_propagations: [{from: D_X, to: G_Z}]
+required_services: [{id: Required, functional_service_type: X, demands: [D_X]}]
+provided_services: [{id: Provided, functional_service_type: Z, guarantees: [G_Z]}]
+"#
+                .into(),
+            )
+            .unwrap(),
+        );
+
+        let result = SystemOfSystems::new().compose(vec![b, a]);
+        match result {
+            Err(ConSertError::Composition {
+                source:
+                    CompositionError::Incompatible {
+                        path,
+                        unmatched_demands,
+                        ..
+                    },
+            }) => {
+                assert_eq!(path, "a.yml");
+                assert_eq!(unmatched_demands.len(), 1);
+            }
+            _ => panic!("a and b should not be composable"),
+        }
+    }
+
+    #[test]
+    fn test_link_consert_providers() {
+        // a cannot be composed, but still provides the guarantee c demands
+        let a = Rc::new(
+            Consert::from_yaml(
+                &"a.yml",
+                String::new(),
+                r#"
+guarantees:
+  - id: G_X
+    dimensions: [Categorical: {type: Exchange, covered: [X], subset: Demand}]
+evidence: []
+demands:
+  - id: D_Y
+    dimensions: [Categorical: {type: Exchange, covered: [Y], subset: Demand}]
+gates: []
+tree_propagations: [{from: D_Y, to: G_X}]
+required_services: [{id: Required, functional_service_type: Y, demands: [D_Y]}]
+provided_services: [{id: Provided, functional_service_type: X, guarantees: [G_X]}]
+"#
+                .into(),
+            )
+            .unwrap(),
+        );
+        let mut c = Consert::from_yaml(
+            &"c.yml",
+            String::new(),
+            r#"
+guarantees:
+  - id: G_Z
+    dimensions: [Categorical: {type: Exchange, covered: [Z], subset: Demand}]
+evidence: []
+demands:
+  - id: D_X
+    dimensions: [Categorical: {type: Exchange, covered: [X], subset: Demand}]
+gates: []
+tree_propagations: [{from: D_X, to: G_Z}]
+required_services: [{id: Required, functional_service_type: X, demands: [D_X]}]
+provided_services: [{id: Provided, functional_service_type: Z, guarantees: [G_Z]}]
+"#
+            .into(),
+        )
+        .unwrap();
+
+        let sos = SystemOfSystems::new().providers(vec![a]);
+        sos.link_consert(&mut c).unwrap();
+        let demand = c.demands()[0].clone();
+        assert_eq!(demand.lock().unwrap().linked_guarantees.len(), 1);
+        assert!(sos.compose(vec![]).is_err());
+    }
+
+    #[test]
+    fn test_compose_service_type() {
+        // the only guarantee fulfilling the demand is provided in a service of another type
+        let model = r#"
+guarantees:
+  - id: G_X
+    dimensions: [Categorical: {type: Exchange, covered: [X], subset: Demand}]
+evidence: []
+demands:
+  - id: D_Y
+    dimensions: [Categorical: {type: Exchange, covered: [Y], subset: Demand}]
+gates: []
+tree_propagations: [{from: D_Y, to: G_X}]
+required_services: [{id: Required, functional_service_type: Y, demands: [D_Y]}]
+provided_services: [{id: Provided, functional_service_type: X, guarantees: [G_X]}]
+"#;
+        let a = Rc::new(Consert::from_yaml(&"a.yml", String::new(), model.into()).unwrap());
+        let typed = Arc::new(Guarantee::new(
+            0,
+            "G_W",
+            None,
+            Dimension::Categorical {
+                r#type: "Exchange".into(),
+                covered: BTreeSet::from_iter(vec!["W".into()]),
+                subset: SubsetRelationship::Demand,
+            },
+            Tree::leaf(ConsertTreeElement::Tautology),
+        ));
+        let other = Arc::new(Guarantee::new(
+            0,
+            "G_Y",
+            None,
+            Dimension::Categorical {
+                r#type: "Exchange".into(),
+                covered: BTreeSet::from_iter(vec!["Y".into()]),
+                subset: SubsetRelationship::Demand,
+            },
+            Tree::leaf(ConsertTreeElement::Tautology),
+        ));
+        let b = Rc::new(Consert::new(
+            "b".into(),
+            "b".into(),
+            String::new(),
+            vec![typed.clone(), other.clone()],
+            vec![],
+            vec![
+                Rc::new(ProvidedService::new("Typed", vec![typed], "Y")),
+                Rc::new(ProvidedService::new("Other", vec![other], "Z")),
+            ],
+            vec![],
+            vec![],
+        ));
+
+        match SystemOfSystems::new().compose(vec![a.clone(), b.clone()]) {
+            Err(ConSertError::Composition {
+                source: CompositionError::Incompatible { explanation, .. },
+            }) => assert_eq!(
+                explanation,
+                "- demand D_Y is unmatched\n  \
+                 - guarantee G_W of b: Exchange {Y} is not inside {W}"
+            ),
+            _ => panic!("the demand should only be matched by a service of type Y"),
+        }
+
+        let (_, composition) = SystemOfSystems::new()
+            .matching(Matching::Relaxed)
+            .compose(vec![a, b.clone()])
+            .unwrap();
+        assert_eq!(composition.bindings.len(), 1);
+        assert_eq!(composition.bindings[0].guarantee, "G_Y");
+
+        // linking follows the matching, too
+        let mut a = Consert::from_yaml(&"a.yml", String::new(), model.into()).unwrap();
+        assert!(SystemOfSystems::new()
+            .providers(vec![b.clone()])
+            .link_consert(&mut a)
+            .is_err());
+        SystemOfSystems::new()
+            .matching(Matching::Relaxed)
+            .providers(vec![b])
+            .link_consert(&mut a)
+            .unwrap();
+        let demand = a.demands()[0].clone();
+        let linked = &demand.lock().unwrap().linked_guarantees;
+        assert_eq!(
+            linked
+                .iter()
+                .map(|(_, g)| g.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["G_Y"]
+        );
+    }
+
     #[test]
     fn test_composition_categorical_dimensions() {
         use std::sync::{Arc, Mutex};
@@ -309,4 +1023,72 @@ mod tests {
 
         assert!(guarantee.fulfills(&demand));
     }
+
+    #[test]
+    fn test_composition_ordinal_dimensions() {
+        use conserts_elements::dimension::OrdinalBound;
+        use std::sync::{Arc, Mutex};
+
+        let demand = Arc::new(Mutex::new(Demand::new(
+            "D",
+            None,
+            Dimension::Ordinal {
+                r#type: "ASIL".into(),
+                levels: vec![
+                    "QM".into(),
+                    "ASIL-A".into(),
+                    "ASIL-B".into(),
+                    "ASIL-C".into(),
+                    "ASIL-D".into(),
+                ],
+                level: "ASIL-B".into(),
+                bound: OrdinalBound::AtLeast,
+                subset: SubsetRelationship::Guarantee,
+            },
+        )));
+
+        // Higher Guarantee than demanded, without listing the levels in between
+        let guarantee = Guarantee::new(
+            0,
+            "G",
+            None,
+            Dimension::Ordinal {
+                r#type: "ASIL".into(),
+                levels: vec![
+                    "QM".into(),
+                    "ASIL-A".into(),
+                    "ASIL-B".into(),
+                    "ASIL-C".into(),
+                    "ASIL-D".into(),
+                ],
+                level: "ASIL-D".into(),
+                bound: OrdinalBound::AtLeast,
+                subset: SubsetRelationship::Guarantee,
+            },
+            Tree::leaf(ConsertTreeElement::Tautology),
+        );
+        assert!(guarantee.fulfills(&demand));
+
+        // Lower Guarantee than demanded
+        let guarantee = Guarantee::new(
+            0,
+            "G",
+            None,
+            Dimension::Ordinal {
+                r#type: "ASIL".into(),
+                levels: vec![
+                    "QM".into(),
+                    "ASIL-A".into(),
+                    "ASIL-B".into(),
+                    "ASIL-C".into(),
+                    "ASIL-D".into(),
+                ],
+                level: "ASIL-A".into(),
+                bound: OrdinalBound::AtLeast,
+                subset: SubsetRelationship::Guarantee,
+            },
+            Tree::leaf(ConsertTreeElement::Tautology),
+        );
+        assert!(!guarantee.fulfills(&demand));
+    }
 }
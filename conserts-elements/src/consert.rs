@@ -194,6 +194,11 @@ impl Consert {
         format!("consert_{}", self.name)
     }
 
+    #[cfg(not(tarpaulin_include))] // getter
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+
     #[cfg(not(tarpaulin_include))] // getter
     pub fn checksum(&self) -> String {
         self.checksum.clone()
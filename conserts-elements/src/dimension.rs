@@ -3,13 +3,15 @@
 // SPDX-License-Identifier: MIT
 
 use crate::demands::Demand;
+use crate::interval_set::IntervalSet;
 use crate::uom::UnitOfMeasure;
 use crate::{elements::numeric_range::NumericRange, services::RequiredService};
-use conserts_error::{ConSertError, UnitOfMeasureError};
+use conserts_error::{ConSertError, ParsingError, UnitOfMeasureError};
+use schemars::JsonSchema;
 use serde::{Deserialize, Deserializer, Serialize};
 use std::collections::BTreeSet;
 
-#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, JsonSchema, PartialEq, Serialize)]
 pub enum Dimension {
     Binary {
         r#type: String,
@@ -17,12 +19,25 @@ pub enum Dimension {
     Categorical {
         r#type: String,
         #[serde(deserialize_with = "ensure_non_empty_categorical")]
+        #[schemars(length(min = 1))]
         covered: BTreeSet<String>,
         subset: SubsetRelationship,
     },
+    /// Categories with a declared ordering, lowest first. The dimension covers all levels
+    /// at least or at most as high as `level`.
+    Ordinal {
+        r#type: String,
+        #[serde(deserialize_with = "ensure_ordered_levels")]
+        #[schemars(length(min = 1))]
+        levels: Vec<String>,
+        level: String,
+        bound: OrdinalBound,
+        subset: SubsetRelationship,
+    },
     Numeric {
         r#type: String,
         #[serde(deserialize_with = "ensure_non_empty_numeric")]
+        #[schemars(length(min = 1))]
         covered: Vec<NumericRange>,
         subset: SubsetRelationship,
         #[serde(skip_serializing_if = "Option::is_none")]
@@ -44,6 +59,20 @@ where
     }
 }
 
+fn ensure_ordered_levels<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let buf = Vec::<String>::deserialize(deserializer)?;
+    if buf.is_empty() {
+        Err(serde::de::Error::custom("The levels must be non-empty."))
+    } else if buf.iter().collect::<BTreeSet<_>>().len() < buf.len() {
+        Err(serde::de::Error::custom("The levels must be unique."))
+    } else {
+        Ok(buf)
+    }
+}
+
 fn ensure_non_empty_numeric<'de, D>(deserializer: D) -> Result<Vec<NumericRange>, D::Error>
 where
     D: Deserializer<'de>,
@@ -67,6 +96,7 @@ impl Dimension {
                 covered: _,
                 subset,
             }
+            | Dimension::Ordinal { subset, .. }
             | Dimension::Numeric {
                 r#type: _,
                 covered: _,
@@ -76,6 +106,42 @@ impl Dimension {
         }
     }
 
+    pub fn r#type(&self) -> &str {
+        match self {
+            Dimension::Binary { r#type }
+            | Dimension::Categorical { r#type, .. }
+            | Dimension::Ordinal { r#type, .. }
+            | Dimension::Numeric { r#type, .. } => r#type,
+        }
+    }
+
+    /// The covered values for messages, e.g. `[0, 5] ms` or `{ASIL-C, ASIL-D}`.
+    pub fn covered_description(&self) -> String {
+        let set = |values: Vec<&str>| format!("{{{}}}", values.join(", "));
+        match self {
+            Dimension::Binary { r#type } => r#type.clone(),
+            Dimension::Categorical { covered, .. } => {
+                set(covered.iter().map(String::as_str).collect())
+            }
+            Dimension::Ordinal { levels, .. } => match self.covered_levels() {
+                // keep the declared ordering
+                Some(covered) => set(levels
+                    .iter()
+                    .map(String::as_str)
+                    .filter(|level| covered.contains(level))
+                    .collect()),
+                None => set(vec![]),
+            },
+            Dimension::Numeric { covered, uom, .. } => {
+                let values = covered.iter().collect::<IntervalSet>();
+                match uom {
+                    Some(uom) => format!("{} {}", values, uom.get_unit_ab()),
+                    None => values.to_string(),
+                }
+            }
+        }
+    }
+
     pub fn subset_of(&self, other: &Dimension) -> SubsetResult {
         match (self, other) {
             (Dimension::Binary { r#type: l_type }, Dimension::Binary { r#type: r_type }) => {
@@ -106,6 +172,30 @@ impl Dimension {
                     SubsetResult::Incompatible
                 }
             }
+            (
+                Dimension::Ordinal {
+                    r#type: l_type,
+                    levels,
+                    ..
+                },
+                Dimension::Ordinal {
+                    r#type: r_type,
+                    levels: o_levels,
+                    ..
+                },
+            ) => {
+                if l_type == r_type && levels == o_levels {
+                    match (self.covered_levels(), other.covered_levels()) {
+                        (Some(covered), Some(o_covered)) if covered.is_subset(&o_covered) => {
+                            SubsetResult::True
+                        }
+                        (Some(_), Some(_)) => SubsetResult::False,
+                        _ => SubsetResult::Incompatible,
+                    }
+                } else {
+                    SubsetResult::Incompatible
+                }
+            }
             (
                 Dimension::Numeric {
                     r#type: l_type,
@@ -122,14 +212,10 @@ impl Dimension {
             ) => {
                 if l_type == r_type {
                     if let Ok(true) = Self::compatible(uom, o_uom) {
-                        let f = uom.clone().map(|uom| uom.factor()).unwrap_or_else(|| 1.0);
-                        let o_f = o_uom.clone().map(|uom| uom.factor()).unwrap_or_else(|| 1.0);
-
-                        match covered.iter().all(|r| {
-                            o_covered
-                                .iter()
-                                .any(|o_r| r.multiply(f).included_in(&o_r.multiply(o_f)))
-                        }) {
+                        let values = Self::base_values(covered, uom);
+                        let o_values = Self::base_values(o_covered, o_uom);
+
+                        match values.is_subset_of(&o_values) {
                             true => SubsetResult::True,
                             false => SubsetResult::False,
                         }
@@ -144,6 +230,58 @@ impl Dimension {
         }
     }
 
+    /// The levels covered by an ordinal dimension, `None` for other dimensions or an
+    /// undeclared level.
+    pub fn covered_levels(&self) -> Option<BTreeSet<&str>> {
+        match self {
+            Dimension::Ordinal {
+                levels,
+                level,
+                bound,
+                ..
+            } => {
+                let position = levels.iter().position(|l| l.eq(level))?;
+                let covered = match bound {
+                    OrdinalBound::AtLeast => &levels[position..],
+                    OrdinalBound::AtMost => &levels[..=position],
+                };
+                Some(covered.iter().map(String::as_str).collect())
+            }
+            _ => None,
+        }
+    }
+
+    /// The values covered by a numeric dimension in its own unit, `None` for other dimensions.
+    pub fn covered_values(&self) -> Option<IntervalSet> {
+        match self {
+            Dimension::Numeric { covered, .. } => Some(covered.iter().collect()),
+            _ => None,
+        }
+    }
+
+    /// Checks what cannot be checked while reading a single field.
+    pub fn check(&self) -> Result<(), ParsingError> {
+        match self {
+            Dimension::Numeric { r#type, .. }
+                if self
+                    .covered_values()
+                    .is_some_and(|values| values.is_empty()) =>
+            {
+                Err(ParsingError::EmptyRange(r#type.clone()))
+            }
+            Dimension::Ordinal {
+                r#type,
+                levels,
+                level,
+                ..
+            } if !levels.contains(level) => Err(ParsingError::UnknownLevel {
+                r#type: r#type.clone(),
+                level: level.clone(),
+            }),
+            _ => Ok(()),
+        }
+    }
+
     fn compatible(
         uom: &Option<UnitOfMeasure>,
         o_uom: &Option<UnitOfMeasure>,
@@ -154,14 +292,29 @@ impl Dimension {
             _ => Err(UnitOfMeasureError::Incompatible.into()),
         }
     }
+
+    /// The covered values in the base unit of the quantity.
+    fn base_values(covered: &[NumericRange], uom: &Option<UnitOfMeasure>) -> IntervalSet {
+        let values = covered.iter().collect::<IntervalSet>();
+        match uom {
+            Some(uom) => values.shift(uom.offset()).scale(uom.factor()),
+            None => values,
+        }
+    }
 }
 
-#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, JsonSchema, PartialEq, Serialize)]
 pub enum SubsetRelationship {
     Guarantee,
     Demand,
 }
 
+#[derive(Clone, Copy, Debug, Deserialize, Eq, JsonSchema, PartialEq, Serialize)]
+pub enum OrdinalBound {
+    AtLeast,
+    AtMost,
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub enum SubsetResult {
     True,
@@ -258,6 +411,126 @@ mod tests {
         assert_eq!(d1_ms_10000.subset_of(&d2_s_9), SubsetResult::False);
     }
 
+    #[test]
+    fn test_subset_exclusive_ranges() {
+        let above_2_m = Dimension::Numeric {
+            r#type: "Distance".into(),
+            covered: vec![NumericRange::from(">", 2.0).unwrap()],
+            subset: SubsetRelationship::Guarantee,
+            uom: Some(UnitOfMeasure::new("m").unwrap()),
+        };
+        let from_2_m = Dimension::Numeric {
+            r#type: "Distance".into(),
+            covered: vec![NumericRange::from(">=", 2.0).unwrap()],
+            subset: SubsetRelationship::Guarantee,
+            uom: Some(UnitOfMeasure::new("m").unwrap()),
+        };
+        let below_1_m = Dimension::Numeric {
+            r#type: "Distance".into(),
+            covered: vec![NumericRange::from("<", 1.0).unwrap()],
+            subset: SubsetRelationship::Guarantee,
+            uom: Some(UnitOfMeasure::new("m").unwrap()),
+        };
+        let below_2000_mm = Dimension::Numeric {
+            r#type: "Distance".into(),
+            covered: vec![NumericRange::from("<", 2000.0).unwrap()],
+            subset: SubsetRelationship::Guarantee,
+            uom: Some(UnitOfMeasure::new("mm").unwrap()),
+        };
+        let above_1999_mm = Dimension::Numeric {
+            r#type: "Distance".into(),
+            covered: vec![NumericRange::from(">", 1999.0).unwrap()],
+            subset: SubsetRelationship::Guarantee,
+            uom: Some(UnitOfMeasure::new("mm").unwrap()),
+        };
+        assert_eq!(above_2_m.subset_of(&from_2_m), SubsetResult::True);
+        assert_eq!(from_2_m.subset_of(&above_2_m), SubsetResult::False);
+        assert_eq!(below_1_m.subset_of(&below_2000_mm), SubsetResult::True);
+        assert_eq!(above_2_m.subset_of(&above_1999_mm), SubsetResult::True);
+    }
+
+    #[test]
+    fn test_subset_offset_units() {
+        let d1_celsius = Dimension::Numeric {
+            r#type: "Temperature".into(),
+            covered: vec![NumericRange::Inclusive(RangeInclusive::new(10.0, 20.0))],
+            subset: SubsetRelationship::Guarantee,
+            uom: Some(UnitOfMeasure::new("°C").unwrap()),
+        };
+        let d2_kelvin = Dimension::Numeric {
+            r#type: "Temperature".into(),
+            covered: vec![NumericRange::Inclusive(RangeInclusive::new(283.0, 294.0))],
+            subset: SubsetRelationship::Guarantee,
+            uom: Some(UnitOfMeasure::new("K").unwrap()),
+        };
+        let d2_kelvin_284 = Dimension::Numeric {
+            r#type: "Temperature".into(),
+            covered: vec![NumericRange::Inclusive(RangeInclusive::new(284.0, 294.0))],
+            subset: SubsetRelationship::Guarantee,
+            uom: Some(UnitOfMeasure::new("K").unwrap()),
+        };
+        let d3_fahrenheit = Dimension::Numeric {
+            r#type: "Temperature".into(),
+            covered: vec![NumericRange::Inclusive(RangeInclusive::new(50.0, 68.0))],
+            subset: SubsetRelationship::Guarantee,
+            uom: Some(UnitOfMeasure::new("°F").unwrap()),
+        };
+        let d4_celsius = Dimension::Numeric {
+            r#type: "Temperature".into(),
+            covered: vec![NumericRange::Inclusive(RangeInclusive::new(9.9, 20.1))],
+            subset: SubsetRelationship::Guarantee,
+            uom: Some(UnitOfMeasure::new("°C").unwrap()),
+        };
+        assert_eq!(d1_celsius.subset_of(&d2_kelvin), SubsetResult::True);
+        assert_eq!(d1_celsius.subset_of(&d2_kelvin_284), SubsetResult::False);
+        assert_eq!(d3_fahrenheit.subset_of(&d4_celsius), SubsetResult::True);
+    }
+
+    #[test]
+    fn test_subset_union_of_ranges() {
+        let split = Dimension::Numeric {
+            r#type: "Value".into(),
+            covered: vec![
+                NumericRange::Inclusive(RangeInclusive::new(0.0, 5.0)),
+                NumericRange::Inclusive(RangeInclusive::new(5.0, 10.0)),
+            ],
+            subset: SubsetRelationship::Demand,
+            uom: None,
+        };
+        let middle = Dimension::Numeric {
+            r#type: "Value".into(),
+            covered: vec![NumericRange::Inclusive(RangeInclusive::new(2.0, 8.0))],
+            subset: SubsetRelationship::Demand,
+            uom: None,
+        };
+        let gap = Dimension::Numeric {
+            r#type: "Value".into(),
+            covered: vec![
+                NumericRange::Exclusive(0.0..5.0),
+                NumericRange::Exclusive(5.0..10.0),
+            ],
+            subset: SubsetRelationship::Demand,
+            uom: None,
+        };
+        assert_eq!(middle.subset_of(&split), SubsetResult::True);
+        assert_eq!(split.subset_of(&middle), SubsetResult::False);
+        assert_eq!(middle.subset_of(&gap), SubsetResult::False);
+    }
+
+    #[test]
+    fn test_check_empty_range() {
+        let empty = Dimension::Numeric {
+            r#type: "Value".into(),
+            covered: vec![NumericRange::Inclusive(RangeInclusive::new(5.0, 3.0))],
+            subset: SubsetRelationship::Demand,
+            uom: None,
+        };
+        assert_eq!(
+            empty.check(),
+            Err(conserts_error::ParsingError::EmptyRange("Value".into()))
+        );
+    }
+
     #[test]
     fn test_non_subset_compatible_uom() {
         let d1 = Dimension::Numeric {
@@ -308,4 +581,81 @@ mod tests {
         };
         assert_eq!(d1.subset_of(&d2), SubsetResult::Incompatible);
     }
+
+    #[test]
+    fn test_subset_ordinal() {
+        use super::OrdinalBound::{AtLeast, AtMost};
+        let levels: Vec<String> = vec![
+            "QM".into(),
+            "ASIL-A".into(),
+            "ASIL-B".into(),
+            "ASIL-C".into(),
+            "ASIL-D".into(),
+        ];
+        let at_least_a = Dimension::Ordinal {
+            r#type: "Integrity".into(),
+            levels: levels.clone(),
+            level: "ASIL-A".into(),
+            bound: AtLeast,
+            subset: SubsetRelationship::Guarantee,
+        };
+        let at_least_b = Dimension::Ordinal {
+            r#type: "Integrity".into(),
+            levels: levels.clone(),
+            level: "ASIL-B".into(),
+            bound: AtLeast,
+            subset: SubsetRelationship::Guarantee,
+        };
+        let at_least_d = Dimension::Ordinal {
+            r#type: "Integrity".into(),
+            levels: levels.clone(),
+            level: "ASIL-D".into(),
+            bound: AtLeast,
+            subset: SubsetRelationship::Guarantee,
+        };
+        let at_most_a = Dimension::Ordinal {
+            r#type: "Integrity".into(),
+            levels: levels.clone(),
+            level: "ASIL-A".into(),
+            bound: AtMost,
+            subset: SubsetRelationship::Guarantee,
+        };
+        let at_most_b = Dimension::Ordinal {
+            r#type: "Integrity".into(),
+            levels: levels.clone(),
+            level: "ASIL-B".into(),
+            bound: AtMost,
+            subset: SubsetRelationship::Guarantee,
+        };
+        // a level that is not declared
+        let at_least_e = Dimension::Ordinal {
+            r#type: "Integrity".into(),
+            levels,
+            level: "ASIL-E".into(),
+            bound: AtLeast,
+            subset: SubsetRelationship::Guarantee,
+        };
+
+        assert_eq!(at_least_d.subset_of(&at_least_b), SubsetResult::True);
+        assert_eq!(at_least_a.subset_of(&at_least_b), SubsetResult::False);
+        assert_eq!(at_most_a.subset_of(&at_most_b), SubsetResult::True);
+        assert_eq!(at_least_b.subset_of(&at_most_b), SubsetResult::False);
+        assert_eq!(
+            at_least_e.subset_of(&at_least_b),
+            SubsetResult::Incompatible
+        );
+        assert!(at_least_e.check().is_err());
+
+        let other_ordering = Dimension::Ordinal {
+            r#type: "Integrity".into(),
+            levels: vec!["ASIL-B".into(), "ASIL-D".into()],
+            level: "ASIL-D".into(),
+            bound: AtLeast,
+            subset: SubsetRelationship::Guarantee,
+        };
+        assert_eq!(
+            other_ordering.subset_of(&at_least_b),
+            SubsetResult::Incompatible
+        );
+    }
 }
@@ -4,8 +4,11 @@
 
 pub mod consert_tree;
 pub mod demands;
+pub mod evaluation;
 pub mod evidence;
 pub mod guarantees;
+pub mod integrity;
+pub mod interval_set;
 pub mod numeric_range;
 pub mod services;
 pub mod uom;
@@ -7,7 +7,9 @@ use crate::elements::evidence::Evidence;
 use crate::elements::guarantees::GuaranteePropagation;
 use crate::elements::services::RequiredService;
 use conserts_error::{ConSertError, ParsingError};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt::Debug;
 use std::sync::{Arc, Mutex};
 
@@ -19,7 +21,7 @@ impl Default for ConsertTree {
     }
 }
 
-#[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Eq, JsonSchema, PartialEq, Serialize, Deserialize)]
 pub struct Gate {
     pub id: String,
     #[serde(skip)]
@@ -45,32 +47,84 @@ impl Gate {
     }
 }
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Copy, Clone, Debug, Eq, JsonSchema, PartialEq, Serialize, Deserialize)]
 pub enum GateFunction {
     And,
     Or,
 }
 
+/// Subtrees can be shared between several parents, e.g. a gate feeding two guarantees, so
+/// cloning a tree does not copy its nodes.
 #[derive(Debug)]
 pub struct Tree<T> {
-    pub data: Box<TreeNode<T>>,
+    pub data: Arc<TreeNode<T>>,
 }
 
-impl<T> Tree<T> {
-    pub fn leaf(element: T) -> Self {
+impl<T> Clone for Tree<T> {
+    fn clone(&self) -> Self {
         Self {
-            data: Box::new(TreeNode {
-                element,
-                children: vec![],
-            }),
+            data: self.data.clone(),
         }
     }
+}
+
+impl<T> Tree<T> {
+    pub fn leaf(element: T) -> Self {
+        Self::node(element, vec![])
+    }
 
     pub fn node(element: T, children: Vec<Tree<T>>) -> Self {
         Self {
-            data: Box::new(TreeNode { element, children }),
+            data: Arc::new(TreeNode { element, children }),
         }
     }
+
+    /// Whether both trees are the same shared subtree.
+    pub fn shares(&self, other: &Tree<T>) -> bool {
+        Arc::ptr_eq(&self.data, &other.data)
+    }
+}
+
+/// The subtrees grown from the propagations of a ConSert. Every element is only grown once,
+/// so that elements with several parents are shared, and a cycle in the propagations is
+/// reported instead of growing endlessly.
+#[derive(Default)]
+pub struct Subtrees {
+    grown: BTreeMap<String, ConsertTree>,
+    growing: Vec<String>,
+}
+
+impl Subtrees {
+    pub fn grow<F>(
+        &mut self,
+        id: &str,
+        grow: F,
+    ) -> Result<ConsertTree, ConSertError<Demand, RequiredService>>
+    where
+        F: FnOnce(&mut Self) -> Result<ConsertTree, ConSertError<Demand, RequiredService>>,
+    {
+        if let Some(tree) = self.grown.get(id) {
+            return Ok(tree.clone());
+        }
+        if let Some(start) = self.growing.iter().position(|growing| growing.eq(id)) {
+            // trees grow against the direction of the propagations
+            let cycle = self.growing[start..]
+                .iter()
+                .map(String::as_str)
+                .chain(std::iter::once(id))
+                .rev()
+                .map(String::from)
+                .collect();
+            return Err(ParsingError::Cycle(cycle).into());
+        }
+
+        self.growing.push(id.to_string());
+        let tree = grow(self);
+        let _ = self.growing.pop();
+        let tree = tree?;
+        let _ = self.grown.insert(id.to_string(), tree.clone());
+        Ok(tree)
+    }
 }
 
 #[derive(Debug)]
@@ -203,28 +257,32 @@ impl Debug for ConsertTreeElement {
 
 pub fn grow_cst(
     path: &str,
+    subtrees: &mut Subtrees,
     propagations: &[GuaranteePropagation],
     runtime_evidence: &[Arc<Evidence>],
     gates: &[Gate],
     demands: &[Arc<Mutex<Demand>>],
 ) -> Result<ConsertTree, ConSertError<Demand, RequiredService>> {
-    let element = ConsertTreeElement::from_path(path, runtime_evidence, gates, demands)?;
-
-    let children = propagations
-        .iter()
-        .filter(|gp| gp.target_path().eq(path))
-        .map(|gp| {
-            grow_cst(
-                &gp.source_path(),
-                propagations,
-                runtime_evidence,
-                gates,
-                demands,
-            )
-        })
-        .collect::<Result<Vec<ConsertTree>, ConSertError<Demand, RequiredService>>>()?;
-
-    Ok(Tree {
-        data: Box::new(TreeNode { element, children }),
+    // all paths refer to the same ConSert, the element is named by the last segment
+    let id = path.rsplit('/').next().unwrap_or(path);
+    subtrees.grow(id, |subtrees| {
+        let element = ConsertTreeElement::from_path(path, runtime_evidence, gates, demands)?;
+
+        let children = propagations
+            .iter()
+            .filter(|gp| gp.target_path().eq(path))
+            .map(|gp| {
+                grow_cst(
+                    &gp.source_path(),
+                    subtrees,
+                    propagations,
+                    runtime_evidence,
+                    gates,
+                    demands,
+                )
+            })
+            .collect::<Result<Vec<ConsertTree>, ConSertError<Demand, RequiredService>>>()?;
+
+        Ok(Tree::node(element, children))
     })
 }
@@ -3,22 +3,29 @@
 // SPDX-License-Identifier: MIT
 
 use crate::dimension::Dimension;
-use crate::elements::guarantees::Guarantee;
+use crate::elements::guarantees::{rank, Guarantee};
 use crate::elements::services::RequiredService;
+use crate::integrity::IntegrityLevel;
 use conserts_error::ConSertError;
 use inflector::Inflector;
+use schemars::JsonSchema;
 use serde::{Deserialize, Deserializer, Serialize};
 use std::sync::Arc;
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, JsonSchema, Serialize)]
 pub struct Demand {
     pub id: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     #[serde(deserialize_with = "ensure_non_empty")]
+    #[schemars(length(min = 1))]
     pub dimensions: Vec<Dimension>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub integrity: Option<IntegrityLevel>,
     #[serde(skip)]
     pub index: usize,
+    /// The guarantees of other ConSerts fulfilling the demand with the name of their crate,
+    /// from the strongest to the weakest.
     #[serde(skip)]
     pub linked_guarantees: Vec<(String, Arc<Guarantee>)>,
 }
@@ -48,6 +55,7 @@ impl Demand {
             description,
             linked_guarantees: vec![],
             dimensions: vec![dimension],
+            integrity: None,
         }
     }
 
@@ -64,6 +72,7 @@ impl Demand {
             description,
             linked_guarantees: vec![],
             dimensions: vec![dimension],
+            integrity: None,
         })
     }
 
@@ -77,6 +86,7 @@ impl Demand {
 
     pub fn link(&mut self, crate_name: String, guarantee: Arc<Guarantee>) {
         self.linked_guarantees.push((crate_name, guarantee));
+        rank(&mut self.linked_guarantees, |(_, guarantee)| guarantee);
     }
 
     pub fn guarantees(&self) -> Vec<(String, Arc<Guarantee>)> {
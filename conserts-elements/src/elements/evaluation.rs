@@ -0,0 +1,390 @@
+// SPDX-FileCopyrightText: 2022 Fraunhofer Institute for Experimental Software Engineering IESE
+//
+// SPDX-License-Identifier: MIT
+
+use crate::consert::Consert;
+use crate::elements::consert_tree::{ConsertTree, ConsertTreeElement, GateFunction};
+use crate::elements::guarantees::rank;
+use crate::elements::{demands::Demand, services::RequiredService};
+use crate::uom::UnitOfMeasure;
+use conserts_error::{ConSertError, EvaluationError};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A runtime value of the property of an evidence or demand.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Boolean(bool),
+    /// A category of a categorical or a level of an ordinal dimension.
+    Category(String),
+    /// A number in the unit of the evidence, or a unitless number.
+    Number(f64),
+    Quantity(f64, UnitOfMeasure),
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Boolean(value) => write!(f, "{}", value),
+            Value::Category(category) => write!(f, "{}", category),
+            Value::Number(value) => write!(f, "{}", value),
+            Value::Quantity(value, uom) => write!(f, "{} {}", value, uom.get_unit_ab()),
+        }
+    }
+}
+
+/// A snapshot of runtime values by the id of their evidence or demand. Demands take a
+/// boolean, whether a provider currently fulfills them.
+pub type Properties = BTreeMap<String, Value>;
+
+/// The result of evaluating a ConSert against a snapshot of runtime properties.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Evaluation {
+    /// Whether the evidence is fulfilled, by id.
+    pub evidence: BTreeMap<String, bool>,
+    /// Whether the demand is fulfilled, by id.
+    pub demands: BTreeMap<String, bool>,
+    /// Whether the guarantee holds, in the order of the model.
+    pub guarantees: Vec<(String, bool)>,
+    /// Evidence and demands without a value, which are not fulfilled like in the generated
+    /// monitors.
+    pub unknown: BTreeSet<String>,
+}
+
+impl Evaluation {
+    /// The guarantees that hold, in the order of the model.
+    pub fn holding(&self) -> impl Iterator<Item = &str> {
+        self.guarantees
+            .iter()
+            .filter(|(_, holds)| *holds)
+            .map(|(id, _)| id.as_str())
+    }
+
+    /// The best guarantee that holds: the first one of the model that no other holding
+    /// guarantee dominates, as ranked when guarantees are linked to demands.
+    pub fn best(&self, consert: &Consert) -> Option<&str> {
+        let mut holding = consert
+            .guarantees()
+            .into_iter()
+            .filter(|guarantee| self.holding().any(|id| id.eq(&guarantee.id)))
+            .collect::<Vec<_>>();
+        rank(&mut holding, |guarantee| guarantee.as_ref());
+        let best = holding.first()?;
+        self.holding().find(|id| best.id.eq(id))
+    }
+}
+
+/// Evaluates every evidence, demand and guarantee of the ConSert without generating code.
+pub fn evaluate(
+    consert: &Consert,
+    properties: &Properties,
+) -> Result<Evaluation, ConSertError<Demand, RequiredService>> {
+    #![allow(clippy::unwrap_used)]
+    let evidence = consert.evidence();
+    let demands = consert
+        .demands()
+        .iter()
+        .map(|demand| demand.lock().unwrap().id.clone())
+        .collect::<Vec<_>>();
+    if let Some(id) = properties
+        .keys()
+        .find(|id| !evidence.iter().any(|e| e.id.eq(*id)) && !demands.contains(id))
+    {
+        return Err(EvaluationError::UnknownProperty(id.clone()).into());
+    }
+
+    let mut evaluation = Evaluation::default();
+    for evidence in &evidence {
+        let fulfilled = match properties.get(&evidence.id) {
+            Some(value) => evidence.evaluate(value)?,
+            None => {
+                let _ = evaluation.unknown.insert(evidence.id.clone());
+                false
+            }
+        };
+        let _ = evaluation.evidence.insert(evidence.id.clone(), fulfilled);
+    }
+    for id in demands {
+        let fulfilled = match properties.get(&id) {
+            Some(Value::Boolean(value)) => *value,
+            Some(value) => {
+                return Err(EvaluationError::WrongValue {
+                    id,
+                    value: value.to_string(),
+                    expected: "a boolean".to_string(),
+                }
+                .into())
+            }
+            None => {
+                let _ = evaluation.unknown.insert(id.clone());
+                false
+            }
+        };
+        let _ = evaluation.demands.insert(id, fulfilled);
+    }
+    evaluation.guarantees = consert
+        .guarantees()
+        .iter()
+        .map(|guarantee| (guarantee.id.clone(), holds(&guarantee.cst, &evaluation)))
+        .collect();
+    Ok(evaluation)
+}
+
+/// Evaluates the tree like the generated guarantees: gates without inputs are neutral.
+fn holds(cst: &ConsertTree, evaluation: &Evaluation) -> bool {
+    #![allow(clippy::unwrap_used)]
+    let children = &cst.data.children;
+    match &cst.data.element {
+        ConsertTreeElement::RuntimeEvidence(_, evidence) => evaluation
+            .evidence
+            .get(&evidence.id)
+            .copied()
+            .unwrap_or(false),
+        ConsertTreeElement::Demand(_, demand) => {
+            let id = &demand.lock().unwrap().id;
+            evaluation.demands.get(id).copied().unwrap_or(false)
+        }
+        ConsertTreeElement::Gate(_, _, GateFunction::And) => {
+            children.iter().all(|child| holds(child, evaluation))
+        }
+        ConsertTreeElement::Gate(_, _, GateFunction::Or) => {
+            children.iter().any(|child| holds(child, evaluation))
+        }
+        ConsertTreeElement::Tautology => true,
+        ConsertTreeElement::Contradiction => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consert::ConsertBuilder;
+    use crate::consert_tree::Tree;
+    use crate::dimension::{Dimension, OrdinalBound, SubsetRelationship};
+    use crate::numeric_range::NumericRange;
+    use conserts_error::UnitOfMeasureError;
+    use pretty_assertions::assert_eq;
+    use std::sync::{Arc, Mutex};
+
+    fn consert() -> Consert {
+        let mut builder = ConsertBuilder::new().name("test").path("test.yml");
+        let distance = builder.add_runtime_evidence(
+            "E_Distance",
+            None,
+            Dimension::Numeric {
+                r#type: "Distance".into(),
+                covered: vec![NumericRange::from("<", 40.0).unwrap()],
+                subset: SubsetRelationship::Demand,
+                uom: Some(UnitOfMeasure::new("m").unwrap()),
+            },
+        );
+        let approved = builder.add_runtime_evidence(
+            "E_Approved",
+            None,
+            Dimension::Binary {
+                r#type: "Approved".into(),
+            },
+        );
+        let asil = builder.add_runtime_evidence(
+            "E_Integrity",
+            None,
+            Dimension::Ordinal {
+                r#type: "Integrity".into(),
+                levels: vec!["A".into(), "B".into(), "C".into(), "D".into()],
+                level: "C".into(),
+                bound: OrdinalBound::AtLeast,
+                subset: SubsetRelationship::Demand,
+            },
+        );
+        let demand = Arc::new(Mutex::new(Demand::new(
+            "D_Position",
+            None,
+            Dimension::Binary {
+                r#type: "Position".into(),
+            },
+        )));
+        let high = Tree::node(
+            ConsertTreeElement::Gate("G".into(), 0, GateFunction::And),
+            vec![
+                Tree::leaf(distance.clone().into()),
+                Tree::leaf(asil.clone().into()),
+                Tree::leaf(ConsertTreeElement::Demand(0, demand.clone())),
+            ],
+        );
+        let low = Tree::node(
+            ConsertTreeElement::Gate("G".into(), 0, GateFunction::Or),
+            vec![Tree::leaf(approved.clone().into()), high.clone()],
+        );
+        builder
+            .add_demand(demand)
+            .add_guarantee(
+                "G_High",
+                None,
+                Dimension::Binary {
+                    r#type: "High".into(),
+                },
+                high,
+            )
+            .add_guarantee(
+                "G_Low",
+                None,
+                Dimension::Binary {
+                    r#type: "Low".into(),
+                },
+                low,
+            )
+            .add_guarantee(
+                "G_Always",
+                None,
+                Dimension::Binary {
+                    r#type: "Always".into(),
+                },
+                ConsertTree::default(),
+            )
+            .build()
+            .unwrap()
+    }
+
+    fn properties(values: &[(&str, Value)]) -> Properties {
+        values
+            .iter()
+            .map(|(id, value)| (id.to_string(), value.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let consert = consert();
+        let evaluation = evaluate(
+            &consert,
+            &properties(&[
+                ("E_Distance", Value::Number(12.0)),
+                ("E_Approved", Value::Boolean(false)),
+                ("E_Integrity", Value::Category("D".into())),
+                ("D_Position", Value::Boolean(true)),
+            ]),
+        )
+        .unwrap();
+        assert_eq!(
+            evaluation.holding().collect::<Vec<_>>(),
+            vec!["G_High", "G_Low", "G_Always"]
+        );
+        assert_eq!(evaluation.best(&consert), Some("G_High"));
+        assert!(evaluation.unknown.is_empty());
+
+        // the threshold is exclusive, the quantity is converted to meters
+        let evaluation = evaluate(
+            &consert,
+            &properties(&[
+                (
+                    "E_Distance",
+                    Value::Quantity(4000.0, UnitOfMeasure::new("cm").unwrap()),
+                ),
+                ("E_Approved", Value::Boolean(true)),
+                ("E_Integrity", Value::Category("D".into())),
+                ("D_Position", Value::Boolean(true)),
+            ]),
+        )
+        .unwrap();
+        assert_eq!(evaluation.evidence["E_Distance"], false);
+        assert_eq!(
+            evaluation.holding().collect::<Vec<_>>(),
+            vec!["G_Low", "G_Always"]
+        );
+    }
+
+    #[test]
+    fn test_best() {
+        let consert = ConsertBuilder::new()
+            .name("test")
+            .path("test.yml")
+            .add_guarantee(
+                "G_Degraded",
+                None,
+                Dimension::Numeric {
+                    r#type: "Latency".into(),
+                    covered: vec![NumericRange::Inclusive(0.0..=100.0)],
+                    subset: SubsetRelationship::Guarantee,
+                    uom: Some(UnitOfMeasure::new("ms").unwrap()),
+                },
+                ConsertTree::default(),
+            )
+            .add_guarantee(
+                "G_Full",
+                None,
+                Dimension::Numeric {
+                    r#type: "Latency".into(),
+                    covered: vec![NumericRange::Inclusive(0.0..=10.0)],
+                    subset: SubsetRelationship::Guarantee,
+                    uom: Some(UnitOfMeasure::new("ms").unwrap()),
+                },
+                ConsertTree::default(),
+            )
+            .add_guarantee(
+                "G_Approved",
+                None,
+                Dimension::Binary {
+                    r#type: "Approved".into(),
+                },
+                ConsertTree::default(),
+            )
+            .build()
+            .unwrap();
+        let evaluation = evaluate(&consert, &Properties::new()).unwrap();
+        assert_eq!(
+            evaluation.holding().collect::<Vec<_>>(),
+            vec!["G_Degraded", "G_Full", "G_Approved"]
+        );
+        // the full latency dominates the degraded one, approval is not comparable to it
+        assert_eq!(evaluation.best(&consert), Some("G_Full"));
+        assert_eq!(Evaluation::default().best(&consert), None);
+    }
+
+    #[test]
+    fn test_evaluate_unknown() {
+        let evaluation = evaluate(
+            &consert(),
+            &properties(&[("E_Integrity", Value::Category("A".into()))]),
+        )
+        .unwrap();
+        assert_eq!(evaluation.evidence["E_Integrity"], false);
+        assert_eq!(evaluation.demands["D_Position"], false);
+        assert_eq!(
+            evaluation.unknown.iter().collect::<Vec<_>>(),
+            vec!["D_Position", "E_Approved", "E_Distance"]
+        );
+        assert_eq!(evaluation.holding().collect::<Vec<_>>(), vec!["G_Always"]);
+    }
+
+    #[test]
+    fn test_evaluate_errors() {
+        let consert = consert();
+        let error = |values: &[(&str, Value)]| evaluate(&consert, &properties(values)).unwrap_err();
+        assert!(matches!(
+            error(&[("E_Missing", Value::Boolean(true))]),
+            ConSertError::Evaluation {
+                source: EvaluationError::UnknownProperty(_)
+            }
+        ));
+        assert!(matches!(
+            error(&[("E_Approved", Value::Number(1.0))]),
+            ConSertError::Evaluation {
+                source: EvaluationError::WrongValue { .. }
+            }
+        ));
+        assert!(matches!(
+            error(&[("E_Integrity", Value::Category("E".into()))]),
+            ConSertError::Evaluation {
+                source: EvaluationError::UnknownLevel { .. }
+            }
+        ));
+        assert!(matches!(
+            error(&[(
+                "E_Distance",
+                Value::Quantity(1.0, UnitOfMeasure::new("s").unwrap())
+            )]),
+            ConSertError::UnitOfMeasure {
+                source: UnitOfMeasureError::Incompatible
+            }
+        ));
+    }
+}
@@ -5,13 +5,15 @@
 use crate::{
     demands::Demand,
     dimension::{Dimension, SubsetResult},
+    evaluation::Value,
     services::RequiredService,
 };
-use conserts_error::{ConSertError, UnitOfMeasureError};
+use conserts_error::{ConSertError, EvaluationError, UnitOfMeasureError};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
-#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, JsonSchema, PartialEq, Serialize)]
 pub struct Evidence {
     pub id: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -41,4 +43,62 @@ impl Evidence {
             SubsetResult::Incompatible => Err(UnitOfMeasureError::Incompatible.into()),
         }
     }
+
+    /// Whether a runtime value of the evidence's property fulfills the evidence.
+    ///
+    /// Categorical evidence accepts either a category or whether the covered categories hold,
+    /// like the generated monitors. Plain numbers are in the unit of the evidence.
+    pub fn evaluate(&self, value: &Value) -> Result<bool, ConSertError<Demand, RequiredService>> {
+        match (&self.dimension, value) {
+            (Dimension::Binary { .. }, Value::Boolean(value))
+            | (Dimension::Categorical { .. }, Value::Boolean(value)) => Ok(*value),
+            (Dimension::Categorical { covered, .. }, Value::Category(category)) => {
+                Ok(covered.contains(category))
+            }
+            (Dimension::Ordinal { levels, .. }, Value::Category(level)) => {
+                if !levels.contains(level) {
+                    return Err(EvaluationError::UnknownLevel {
+                        id: self.id.clone(),
+                        level: level.clone(),
+                    }
+                    .into());
+                }
+                Ok(self
+                    .dimension
+                    .covered_levels()
+                    .is_some_and(|covered| covered.contains(level.as_str())))
+            }
+            (Dimension::Numeric { covered, .. }, Value::Number(value)) => {
+                Ok(covered.iter().any(|range| range.contains(*value)))
+            }
+            (
+                Dimension::Numeric {
+                    covered,
+                    uom: Some(uom),
+                    ..
+                },
+                Value::Quantity(value, unit),
+            ) => {
+                let _ = uom.compatible(unit)?;
+                let value = unit.convert(*value, uom);
+                Ok(covered.iter().any(|range| range.contains(value)))
+            }
+            (Dimension::Numeric { uom: None, .. }, Value::Quantity(_, _)) => {
+                Err(UnitOfMeasureError::Incompatible.into())
+            }
+            (dimension, value) => Err(EvaluationError::WrongValue {
+                id: self.id.clone(),
+                value: value.to_string(),
+                expected: match dimension {
+                    Dimension::Binary { .. } => "a boolean",
+                    Dimension::Categorical { .. } => "a category or a boolean",
+                    Dimension::Ordinal { .. } => "a level",
+                    Dimension::Numeric { uom: Some(_), .. } => "a number or a quantity",
+                    Dimension::Numeric { uom: None, .. } => "a number",
+                }
+                .to_string(),
+            }
+            .into()),
+        }
+    }
 }
@@ -5,16 +5,22 @@
 use crate::dimension::{Dimension, SubsetRelationship, SubsetResult};
 use crate::elements::consert_tree::*;
 use crate::elements::demands::Demand;
+use crate::integrity::IntegrityLevel;
+use crate::uom::UnitOfMeasure;
+use schemars::JsonSchema;
 use serde::{Deserialize, Deserializer, Serialize};
 use std::sync::{Arc, Mutex};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, JsonSchema, Serialize)]
 pub struct Guarantee {
     pub id: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     #[serde(deserialize_with = "ensure_non_empty")]
+    #[schemars(length(min = 1))]
     pub dimensions: Vec<Dimension>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub integrity: Option<IntegrityLevel>,
     #[serde(skip)]
     pub index: usize,
     #[serde(skip)]
@@ -51,6 +57,7 @@ impl Guarantee {
             index,
             description,
             dimensions: vec![dimension],
+            integrity: None,
             cst,
         }
     }
@@ -60,39 +67,114 @@ impl Guarantee {
         self.index
     }
 
+    /// A guarantee fulfills a demand if it covers all dimensions of the demand with at least
+    /// the demanded integrity level.
     pub fn fulfills(&self, demand: &Arc<Mutex<Demand>>) -> bool {
         #![allow(clippy::unwrap_used)]
+        self.mismatches(&demand.lock().unwrap()).is_empty()
+    }
 
-        demand
-            .lock()
-            .unwrap()
-            .dimensions
-            .iter()
-            .all(|demand_dimension| {
-                self.dimensions.iter().any(|guarantee_dimension| {
-                    if guarantee_dimension.subset().eq(&demand_dimension.subset()) {
-                        match guarantee_dimension.subset() {
-                            Some(s) => {
-                                let (lop, rop) = match s {
-                                    SubsetRelationship::Guarantee => {
-                                        (guarantee_dimension, demand_dimension)
-                                    }
-                                    SubsetRelationship::Demand => {
-                                        (demand_dimension, guarantee_dimension)
-                                    }
-                                };
-                                match Dimension::subset_of(lop, rop) {
-                                    SubsetResult::True => true,
-                                    SubsetResult::False | SubsetResult::Incompatible => false,
-                                }
-                            }
-                            None => true,
-                        }
-                    } else {
-                        false
-                    }
-                })
-            })
+    /// Why the guarantee does not fulfill the demand, one mismatch for the integrity level
+    /// and each dimension of the demand it does not cover. Empty if it fulfills the demand.
+    pub fn mismatches(&self, demand: &Demand) -> Vec<Mismatch> {
+        let mut mismatches = vec![];
+        if let Some(required) = demand.integrity {
+            let integrity = match self.integrity {
+                Some(provided) => provided.satisfies(&required),
+                None => false,
+            };
+            if !integrity {
+                mismatches.push(Mismatch::Integrity {
+                    provided: self.integrity,
+                    required,
+                });
+            }
+        }
+
+        for demand_dimension in demand.dimensions.iter() {
+            if !self
+                .dimensions
+                .iter()
+                .any(|guarantee_dimension| covers(guarantee_dimension, demand_dimension))
+            {
+                mismatches.push(self.mismatch(demand_dimension));
+            }
+        }
+        mismatches
+    }
+
+    /// Explains why no dimension covers the demanded one, looking at the dimension of the
+    /// same type.
+    fn mismatch(&self, demand_dimension: &Dimension) -> Mismatch {
+        let r#type = demand_dimension.r#type().to_string();
+        let guarantee_dimension = match self.dimensions.iter().find(|d| d.r#type() == r#type) {
+            Some(guarantee_dimension) => guarantee_dimension,
+            None => return Mismatch::MissingType { r#type },
+        };
+        if let (Some(guarantee), Some(demand)) =
+            (guarantee_dimension.subset(), demand_dimension.subset())
+        {
+            if guarantee != demand {
+                return Mismatch::SubsetDirection {
+                    r#type,
+                    guarantee,
+                    demand,
+                };
+            }
+        }
+
+        let (lop, rop) = match demand_dimension.subset() {
+            Some(SubsetRelationship::Guarantee) => (guarantee_dimension, demand_dimension),
+            _ => (demand_dimension, guarantee_dimension),
+        };
+        match (
+            Dimension::subset_of(lop, rop),
+            guarantee_dimension,
+            demand_dimension,
+        ) {
+            (SubsetResult::False, _, _) => Mismatch::NotCovered {
+                r#type,
+                values: lop.covered_description(),
+                required: rop.covered_description(),
+            },
+            (
+                SubsetResult::Incompatible,
+                Dimension::Numeric { uom, .. },
+                Dimension::Numeric { uom: o_uom, .. },
+            ) => {
+                let unit = |uom: &Option<UnitOfMeasure>| match uom {
+                    Some(uom) => uom.get_unit_ab().to_string(),
+                    None => "none".to_string(),
+                };
+                Mismatch::IncompatibleUnits {
+                    r#type,
+                    guarantee: unit(uom),
+                    demand: unit(o_uom),
+                }
+            }
+            _ => Mismatch::Incomparable { r#type },
+        }
+    }
+
+    /// A guarantee is at least as strong as another if it has at least its integrity level
+    /// and covers each of its dimensions like it would cover a demand, so it fulfills every
+    /// demand the other one fulfills.
+    pub fn at_least_as_strong(&self, other: &Guarantee) -> bool {
+        let integrity = match (self.integrity, other.integrity) {
+            (_, None) => true,
+            (Some(integrity), Some(other)) => integrity.satisfies(&other),
+            (None, Some(_)) => false,
+        };
+        integrity
+            && other
+                .dimensions
+                .iter()
+                .all(|other| self.dimensions.iter().any(|d| covers(d, other)))
+    }
+
+    /// A guarantee dominates another if it is strictly stronger.
+    pub fn dominates(&self, other: &Guarantee) -> bool {
+        self.at_least_as_strong(other) && !other.at_least_as_strong(self)
     }
 
     pub fn gates_and_guarantee_propagations(&self) -> (Vec<Gate>, Vec<GuaranteePropagation>) {
@@ -125,18 +207,15 @@ impl Guarantee {
         let gates: Vec<Gate> = gates
             .into_iter()
             .flatten()
-            .chain(
-                match &cst.data.element {
-                    ConsertTreeElement::Tautology
-                    | ConsertTreeElement::Contradiction
-                    | ConsertTreeElement::Demand(_, _)
-                    | ConsertTreeElement::RuntimeEvidence(_, _) => vec![],
-                    ConsertTreeElement::Gate(id, index, function) => {
-                        vec![Gate::new(id.clone(), *index, *function)]
-                    }
+            .chain(match &cst.data.element {
+                ConsertTreeElement::Tautology
+                | ConsertTreeElement::Contradiction
+                | ConsertTreeElement::Demand(_, _)
+                | ConsertTreeElement::RuntimeEvidence(_, _) => vec![],
+                ConsertTreeElement::Gate(id, index, function) => {
+                    vec![Gate::new(id.clone(), *index, *function)]
                 }
-                .into_iter(),
-            )
+            })
             .collect();
 
         let guarantee_propagation: Vec<GuaranteePropagation> = guarantee_propagation
@@ -195,6 +274,123 @@ impl ConsertTreeRoot for Guarantee {
     }
 }
 
+/// Whether the dimension of a guarantee covers the dimension of a demand.
+fn covers(guarantee_dimension: &Dimension, demand_dimension: &Dimension) -> bool {
+    if guarantee_dimension.subset().eq(&demand_dimension.subset()) {
+        match guarantee_dimension.subset() {
+            Some(s) => {
+                let (lop, rop) = match s {
+                    SubsetRelationship::Guarantee => (guarantee_dimension, demand_dimension),
+                    SubsetRelationship::Demand => (demand_dimension, guarantee_dimension),
+                };
+                match Dimension::subset_of(lop, rop) {
+                    SubsetResult::True => true,
+                    SubsetResult::False | SubsetResult::Incompatible => false,
+                }
+            }
+            None => true,
+        }
+    } else {
+        false
+    }
+}
+
+/// Orders the items from the strongest to the weakest guarantee: each one is ranked by the
+/// number of items dominating it, items of the same rank keep their order.
+pub fn rank<T, F>(items: &mut Vec<T>, guarantee: F)
+where
+    F: Fn(&T) -> &Guarantee,
+{
+    let ranks = items
+        .iter()
+        .map(|item| {
+            items
+                .iter()
+                .filter(|other| guarantee(other).dominates(guarantee(item)))
+                .count()
+        })
+        .collect::<Vec<_>>();
+    let mut ranked = ranks.into_iter().zip(items.drain(..)).collect::<Vec<_>>();
+    ranked.sort_by_key(|(rank, _)| *rank);
+    items.extend(ranked.into_iter().map(|(_, item)| item));
+}
+
+/// The reason a guarantee does not fulfill a demand.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Mismatch {
+    Integrity {
+        provided: Option<IntegrityLevel>,
+        required: IntegrityLevel,
+    },
+    /// The guarantee has no dimension of the demanded type.
+    MissingType { r#type: String },
+    /// The dimensions disagree on whose covered values must be a subset of the other's.
+    SubsetDirection {
+        r#type: String,
+        guarantee: SubsetRelationship,
+        demand: SubsetRelationship,
+    },
+    IncompatibleUnits {
+        r#type: String,
+        guarantee: String,
+        demand: String,
+    },
+    /// The values of the subset side are not inside the values of the other side.
+    NotCovered {
+        r#type: String,
+        values: String,
+        required: String,
+    },
+    /// The dimensions are of different kinds or declare different levels.
+    Incomparable { r#type: String },
+}
+
+impl std::fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Mismatch::Integrity {
+                provided: Some(provided),
+                required,
+            } => write!(
+                f,
+                "integrity level {} does not satisfy {}",
+                provided, required
+            ),
+            Mismatch::Integrity {
+                provided: None,
+                required,
+            } => write!(f, "no integrity level, {} is demanded", required),
+            Mismatch::MissingType { r#type } => write!(f, "no dimension of type {}", r#type),
+            Mismatch::SubsetDirection {
+                r#type,
+                guarantee,
+                demand,
+            } => write!(
+                f,
+                "{} is a subset of the {:?} in the guarantee but of the {:?} in the demand",
+                r#type, guarantee, demand
+            ),
+            Mismatch::IncompatibleUnits {
+                r#type,
+                guarantee,
+                demand,
+            } => write!(
+                f,
+                "{} has incompatible units {} and {}",
+                r#type, guarantee, demand
+            ),
+            Mismatch::NotCovered {
+                r#type,
+                values,
+                required,
+            } => write!(f, "{} {} is not inside {}", r#type, values, required),
+            Mismatch::Incomparable { r#type } => {
+                write!(f, "{} cannot be compared", r#type)
+            }
+        }
+    }
+}
+
 pub trait ConsertTreeRoot {
     fn identifier(&self) -> String;
     fn cst(&self) -> &ConsertTree;
@@ -341,6 +537,145 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn test_fulfill_integrity() {
+        let demand = Arc::new(Mutex::new(Demand::new(
+            "D",
+            None,
+            Dimension::Binary {
+                r#type: "Speed".into(),
+            },
+        )));
+        let mut guarantee = Guarantee::new(
+            0,
+            "G",
+            None,
+            Dimension::Binary {
+                r#type: "Speed".into(),
+            },
+            Tree::leaf(ConsertTreeElement::Tautology),
+        );
+        assert!(guarantee.fulfills(&demand));
+        guarantee.integrity = Some(IntegrityLevel::AsilB);
+        assert!(guarantee.fulfills(&demand));
+
+        demand.lock().unwrap().integrity = Some(IntegrityLevel::AsilB);
+        guarantee.integrity = None;
+        assert!(!guarantee.fulfills(&demand));
+        guarantee.integrity = Some(IntegrityLevel::AsilD);
+        assert!(guarantee.fulfills(&demand));
+        guarantee.integrity = Some(IntegrityLevel::AsilA);
+        assert!(!guarantee.fulfills(&demand));
+        guarantee.integrity = Some(IntegrityLevel::Sil4);
+        assert!(!guarantee.fulfills(&demand));
+    }
+
+    #[test]
+    fn test_mismatches() {
+        let speed_3_ms = Dimension::Numeric {
+            r#type: "Speed".into(),
+            covered: vec![NumericRange::Inclusive(0.0..=3.0)],
+            subset: SubsetRelationship::Guarantee,
+            uom: Some(crate::uom::UnitOfMeasure::new("ms").unwrap()),
+        };
+        let speed_5_ms = Dimension::Numeric {
+            r#type: "Speed".into(),
+            covered: vec![NumericRange::Inclusive(0.0..=5.0)],
+            subset: SubsetRelationship::Guarantee,
+            uom: Some(crate::uom::UnitOfMeasure::new("ms").unwrap()),
+        };
+        let speed_5_m_s = Dimension::Numeric {
+            r#type: "Speed".into(),
+            covered: vec![NumericRange::Inclusive(0.0..=5.0)],
+            subset: SubsetRelationship::Guarantee,
+            uom: Some(crate::uom::UnitOfMeasure::new("m/s").unwrap()),
+        };
+        let demand_speed_3_ms = Dimension::Numeric {
+            r#type: "Speed".into(),
+            covered: vec![NumericRange::Inclusive(0.0..=3.0)],
+            subset: SubsetRelationship::Demand,
+            uom: Some(crate::uom::UnitOfMeasure::new("ms").unwrap()),
+        };
+        let demand_speed_5_ms = Dimension::Numeric {
+            r#type: "Speed".into(),
+            covered: vec![NumericRange::Inclusive(0.0..=5.0)],
+            subset: SubsetRelationship::Demand,
+            uom: Some(crate::uom::UnitOfMeasure::new("ms").unwrap()),
+        };
+        let demand_speed_8_ms = Dimension::Numeric {
+            r#type: "Speed".into(),
+            covered: vec![NumericRange::Inclusive(0.0..=8.0)],
+            subset: SubsetRelationship::Demand,
+            uom: Some(crate::uom::UnitOfMeasure::new("ms").unwrap()),
+        };
+        let mut guarantee = Guarantee {
+            integrity: Some(IntegrityLevel::AsilC),
+            ..Guarantee::new(
+                0,
+                "G",
+                None,
+                speed_3_ms.clone(),
+                Tree::leaf(ConsertTreeElement::Tautology),
+            )
+        };
+        let mut demand = Demand::new("D", None, speed_5_ms.clone());
+        demand.integrity = Some(IntegrityLevel::AsilB);
+        let messages = |guarantee: &Guarantee, demand: &Demand| {
+            guarantee
+                .mismatches(demand)
+                .iter()
+                .map(Mismatch::to_string)
+                .collect::<Vec<_>>()
+        };
+
+        assert!(messages(&guarantee, &demand).is_empty());
+
+        guarantee.dimensions = vec![speed_5_ms.clone()];
+        demand.dimensions = vec![speed_3_ms.clone()];
+        assert_eq!(
+            messages(&guarantee, &demand),
+            vec!["Speed [0, 5] ms is not inside [0, 3] ms"]
+        );
+
+        guarantee.dimensions = vec![demand_speed_5_ms];
+        demand.dimensions = vec![demand_speed_8_ms];
+        assert_eq!(
+            messages(&guarantee, &demand),
+            vec!["Speed [0, 8] ms is not inside [0, 5] ms"]
+        );
+
+        guarantee.dimensions = vec![speed_5_m_s];
+        demand.dimensions = vec![speed_5_ms];
+        assert_eq!(
+            messages(&guarantee, &demand),
+            vec!["Speed has incompatible units m/s and ms"]
+        );
+
+        guarantee.dimensions = vec![demand_speed_3_ms];
+        assert_eq!(
+            messages(&guarantee, &demand),
+            vec!["Speed is a subset of the Demand in the guarantee but of the Guarantee in the demand"]
+        );
+
+        guarantee.dimensions = vec![Dimension::Binary {
+            r#type: "Approved".into(),
+        }];
+        assert_eq!(
+            messages(&guarantee, &demand),
+            vec!["no dimension of type Speed"]
+        );
+
+        guarantee.dimensions = vec![speed_3_ms];
+        demand.integrity = Some(IntegrityLevel::AsilD);
+        assert_eq!(
+            guarantee.mismatches(&demand),
+            vec![Mismatch::Integrity {
+                provided: Some(IntegrityLevel::AsilC),
+                required: IntegrityLevel::AsilD
+            }]
+        );
+    }
+
     #[test]
     fn test_fulfill_dimensions() {
         let demand = &Arc::new(Mutex::new(Demand {
@@ -359,6 +694,7 @@ pub mod tests {
                     subset: SubsetRelationship::Demand,
                 },
             ],
+            integrity: None,
             index: 0,
             linked_guarantees: vec![],
         }));
@@ -367,6 +703,7 @@ pub mod tests {
             id: "G".into(),
             description: None,
             dimensions: vec![],
+            integrity: None,
             index: 0,
             cst: Tree::leaf(ConsertTreeElement::Tautology),
         };
@@ -382,6 +719,7 @@ pub mod tests {
                 subset: SubsetRelationship::Demand,
                 uom: Some(crate::uom::UnitOfMeasure::new("m/s").unwrap()),
             }],
+            integrity: None,
             index: 0,
             cst: Tree::leaf(ConsertTreeElement::Tautology),
         };
@@ -404,6 +742,7 @@ pub mod tests {
                     subset: SubsetRelationship::Demand,
                 },
             ],
+            integrity: None,
             index: 0,
             cst: Tree::leaf(ConsertTreeElement::Tautology),
         };
@@ -431,6 +770,7 @@ pub mod tests {
                     subset: SubsetRelationship::Demand,
                 },
             ],
+            integrity: None,
             index: 0,
             cst: Tree::leaf(ConsertTreeElement::Tautology),
         };
@@ -464,10 +804,92 @@ pub mod tests {
                     uom: Some(crate::uom::UnitOfMeasure::new("s").unwrap()),
                 },
             ],
+            integrity: None,
             index: 0,
             cst: Tree::leaf(ConsertTreeElement::Tautology),
         };
 
         assert!(guarantee_more_dimension.fulfills(demand));
     }
+
+    #[test]
+    fn test_rank() {
+        let degraded = Guarantee {
+            integrity: Some(IntegrityLevel::AsilB),
+            ..Guarantee::new(
+                0,
+                "Degraded",
+                None,
+                Dimension::Numeric {
+                    r#type: "Latency".into(),
+                    covered: vec![NumericRange::Inclusive(0.0..=100.0)],
+                    subset: SubsetRelationship::Guarantee,
+                    uom: Some(crate::uom::UnitOfMeasure::new("ms").unwrap()),
+                },
+                Tree::leaf(ConsertTreeElement::Tautology),
+            )
+        };
+        let full = Guarantee {
+            integrity: Some(IntegrityLevel::AsilB),
+            ..Guarantee::new(
+                0,
+                "Full",
+                None,
+                Dimension::Numeric {
+                    r#type: "Latency".into(),
+                    covered: vec![NumericRange::Inclusive(0.0..=10.0)],
+                    subset: SubsetRelationship::Guarantee,
+                    uom: Some(crate::uom::UnitOfMeasure::new("ms").unwrap()),
+                },
+                Tree::leaf(ConsertTreeElement::Tautology),
+            )
+        };
+        let certified = Guarantee {
+            integrity: Some(IntegrityLevel::AsilD),
+            ..Guarantee::new(
+                0,
+                "Certified",
+                None,
+                Dimension::Numeric {
+                    r#type: "Latency".into(),
+                    covered: vec![NumericRange::Inclusive(0.0..=100.0)],
+                    subset: SubsetRelationship::Guarantee,
+                    uom: Some(crate::uom::UnitOfMeasure::new("ms").unwrap()),
+                },
+                Tree::leaf(ConsertTreeElement::Tautology),
+            )
+        };
+        let other = Guarantee {
+            integrity: Some(IntegrityLevel::AsilB),
+            ..Guarantee::new(
+                0,
+                "Other",
+                None,
+                Dimension::Numeric {
+                    r#type: "Latency".into(),
+                    covered: vec![NumericRange::Inclusive(0.0..=100.0)],
+                    subset: SubsetRelationship::Guarantee,
+                    uom: Some(crate::uom::UnitOfMeasure::new("ms").unwrap()),
+                },
+                Tree::leaf(ConsertTreeElement::Tautology),
+            )
+        };
+
+        assert!(full.dominates(&degraded));
+        assert!(!degraded.dominates(&full));
+        assert!(certified.dominates(&degraded));
+        // neither is stronger in every respect
+        assert!(!full.dominates(&certified));
+        assert!(!certified.dominates(&full));
+        // equally strong guarantees do not dominate each other
+        assert!(other.at_least_as_strong(&degraded));
+        assert!(!other.dominates(&degraded));
+
+        let mut guarantees = vec![degraded, other, full, certified];
+        rank(&mut guarantees, |guarantee| guarantee);
+        assert_eq!(
+            guarantees.iter().map(|g| g.id.as_str()).collect::<Vec<_>>(),
+            vec!["Full", "Certified", "Degraded", "Other"]
+        );
+    }
 }
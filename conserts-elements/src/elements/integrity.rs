@@ -0,0 +1,193 @@
+// SPDX-FileCopyrightText: 2022 Fraunhofer Institute for Experimental Software Engineering IESE
+//
+// SPDX-License-Identifier: MIT
+
+use conserts_error::ParsingError;
+use schemars::gen::SchemaGenerator;
+use schemars::schema::{InstanceType, Schema, SchemaObject};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+
+/// The integrity level of a guarantee or demand, either an ASIL (ISO 26262) or a SIL
+/// (IEC 61508). Levels of different standards are not comparable.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum IntegrityLevel {
+    Qm,
+    AsilA,
+    AsilB,
+    AsilC,
+    AsilD,
+    Sil1,
+    Sil2,
+    Sil3,
+    Sil4,
+}
+
+#[derive(PartialEq)]
+enum Standard {
+    Asil,
+    Sil,
+}
+
+const LEVELS: &[IntegrityLevel] = &[
+    IntegrityLevel::Qm,
+    IntegrityLevel::AsilA,
+    IntegrityLevel::AsilB,
+    IntegrityLevel::AsilC,
+    IntegrityLevel::AsilD,
+    IntegrityLevel::Sil1,
+    IntegrityLevel::Sil2,
+    IntegrityLevel::Sil3,
+    IntegrityLevel::Sil4,
+];
+
+impl IntegrityLevel {
+    fn rank(&self) -> (Standard, u8) {
+        match self {
+            IntegrityLevel::Qm => (Standard::Asil, 0),
+            IntegrityLevel::AsilA => (Standard::Asil, 1),
+            IntegrityLevel::AsilB => (Standard::Asil, 2),
+            IntegrityLevel::AsilC => (Standard::Asil, 3),
+            IntegrityLevel::AsilD => (Standard::Asil, 4),
+            IntegrityLevel::Sil1 => (Standard::Sil, 1),
+            IntegrityLevel::Sil2 => (Standard::Sil, 2),
+            IntegrityLevel::Sil3 => (Standard::Sil, 3),
+            IntegrityLevel::Sil4 => (Standard::Sil, 4),
+        }
+    }
+
+    /// Whether an element of this level can be relied on where `required` is demanded.
+    pub fn satisfies(&self, required: &IntegrityLevel) -> bool {
+        matches!(
+            self.partial_cmp(required),
+            Some(Ordering::Greater | Ordering::Equal)
+        )
+    }
+
+    /// Reads levels such as `ASIL B`, `ASIL-B`, `QM` or `SIL 3`. Unknown levels, as
+    /// written by safeTbox, are read as `None`.
+    pub fn parse(text: &str) -> Result<Option<Self>, ParsingError> {
+        let normalized = text
+            .chars()
+            .filter(|c| !c.is_whitespace() && !matches!(c, '-' | '_'))
+            .collect::<String>()
+            .to_uppercase();
+        match normalized.as_str() {
+            "" | "ASILUNKNOWN" | "UNKNOWN" => Ok(None),
+            "QM" | "ASILQM" => Ok(Some(IntegrityLevel::Qm)),
+            "ASILA" => Ok(Some(IntegrityLevel::AsilA)),
+            "ASILB" => Ok(Some(IntegrityLevel::AsilB)),
+            "ASILC" => Ok(Some(IntegrityLevel::AsilC)),
+            "ASILD" => Ok(Some(IntegrityLevel::AsilD)),
+            "SIL1" => Ok(Some(IntegrityLevel::Sil1)),
+            "SIL2" => Ok(Some(IntegrityLevel::Sil2)),
+            "SIL3" => Ok(Some(IntegrityLevel::Sil3)),
+            "SIL4" => Ok(Some(IntegrityLevel::Sil4)),
+            _ => Err(ParsingError::UnsupportedIntegrityLevel(
+                text.trim().to_string(),
+            )),
+        }
+    }
+}
+
+impl PartialOrd for IntegrityLevel {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let (standard, rank) = self.rank();
+        let (o_standard, o_rank) = other.rank();
+        if standard == o_standard {
+            Some(rank.cmp(&o_rank))
+        } else {
+            None
+        }
+    }
+}
+
+impl std::fmt::Display for IntegrityLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.rank() {
+            (Standard::Asil, 0) => write!(f, "QM"),
+            (Standard::Asil, rank) => write!(f, "ASIL {}", (b'A' + rank - 1) as char),
+            (Standard::Sil, rank) => write!(f, "SIL {}", rank),
+        }
+    }
+}
+
+impl TryFrom<String> for IntegrityLevel {
+    type Error = ParsingError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        IntegrityLevel::parse(&value)?.ok_or(ParsingError::UnsupportedIntegrityLevel(value))
+    }
+}
+
+impl From<IntegrityLevel> for String {
+    fn from(level: IntegrityLevel) -> Self {
+        level.to_string()
+    }
+}
+
+impl JsonSchema for IntegrityLevel {
+    #[cfg(not(tarpaulin_include))] // trivial
+    fn schema_name() -> String {
+        "IntegrityLevel".to_string()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        SchemaObject {
+            instance_type: Some(InstanceType::String.into()),
+            enum_values: Some(
+                LEVELS
+                    .iter()
+                    .map(|level| level.to_string().into())
+                    .collect(),
+            ),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            IntegrityLevel::parse(" ASIL B"),
+            Ok(Some(IntegrityLevel::AsilB))
+        );
+        assert_eq!(
+            IntegrityLevel::parse("asil-d"),
+            Ok(Some(IntegrityLevel::AsilD))
+        );
+        assert_eq!(
+            IntegrityLevel::parse("SIL3"),
+            Ok(Some(IntegrityLevel::Sil3))
+        );
+        assert_eq!(IntegrityLevel::parse("QM"), Ok(Some(IntegrityLevel::Qm)));
+        assert_eq!(IntegrityLevel::parse("ASIL UNKNOWN"), Ok(None));
+        assert_eq!(
+            IntegrityLevel::parse("ASIL E"),
+            Err(ParsingError::UnsupportedIntegrityLevel("ASIL E".into()))
+        );
+        for level in LEVELS {
+            assert_eq!(IntegrityLevel::parse(&level.to_string()), Ok(Some(*level)));
+        }
+    }
+
+    #[test]
+    fn test_ordering() {
+        assert!(IntegrityLevel::AsilD.satisfies(&IntegrityLevel::AsilB));
+        assert!(IntegrityLevel::AsilB.satisfies(&IntegrityLevel::AsilB));
+        assert!(IntegrityLevel::AsilA.satisfies(&IntegrityLevel::Qm));
+        assert!(!IntegrityLevel::AsilA.satisfies(&IntegrityLevel::AsilC));
+        assert!(IntegrityLevel::Sil3.satisfies(&IntegrityLevel::Sil2));
+        assert!(!IntegrityLevel::Sil2.satisfies(&IntegrityLevel::Sil3));
+        assert!(!IntegrityLevel::Sil4.satisfies(&IntegrityLevel::AsilA));
+        assert!(!IntegrityLevel::AsilD.satisfies(&IntegrityLevel::Sil1));
+    }
+}
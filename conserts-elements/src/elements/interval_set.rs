@@ -0,0 +1,443 @@
+// SPDX-FileCopyrightText: 2022 Fraunhofer Institute for Experimental Software Engineering IESE
+//
+// SPDX-License-Identifier: MIT
+
+use crate::numeric_range::NumericRange;
+use std::{cmp::Ordering, iter::FromIterator, ops::Bound};
+
+/// A single interval of real numbers. Unlike a `NumericRange`, both ends can be open or
+/// closed independently, which is needed to represent the results of set operations.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Interval {
+    pub lower: Bound<f64>,
+    pub upper: Bound<f64>,
+}
+
+impl Interval {
+    pub fn new(lower: Bound<f64>, upper: Bound<f64>) -> Self {
+        Interval { lower, upper }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        match (self.lower, self.upper) {
+            (Bound::Unbounded, _) | (_, Bound::Unbounded) => false,
+            (Bound::Included(l), Bound::Included(u)) => {
+                l.partial_cmp(&u).is_none_or(Ordering::is_gt)
+            }
+            (Bound::Included(l), Bound::Excluded(u))
+            | (Bound::Excluded(l), Bound::Included(u))
+            | (Bound::Excluded(l), Bound::Excluded(u)) => {
+                l.partial_cmp(&u).is_none_or(Ordering::is_ge)
+            }
+        }
+    }
+
+    pub fn contains(&self, value: f64) -> bool {
+        let lower = match self.lower {
+            Bound::Unbounded => true,
+            Bound::Included(l) => value >= l,
+            Bound::Excluded(l) => value > l,
+        };
+        let upper = match self.upper {
+            Bound::Unbounded => true,
+            Bound::Included(u) => value <= u,
+            Bound::Excluded(u) => value < u,
+        };
+        lower && upper
+    }
+
+    fn intersection(&self, other: &Interval) -> Interval {
+        Interval {
+            lower: std::cmp::max_by(self.lower, other.lower, cmp_lower),
+            upper: std::cmp::min_by(self.upper, other.upper, cmp_upper),
+        }
+    }
+
+    /// Whether the union of both intervals has no gap, given that `self` does not start after `next`.
+    fn connects(&self, next: &Interval) -> bool {
+        match (self.upper, next.lower) {
+            (Bound::Unbounded, _) | (_, Bound::Unbounded) => true,
+            (Bound::Excluded(u), Bound::Excluded(l)) => l < u,
+            (Bound::Included(u), Bound::Included(l))
+            | (Bound::Included(u), Bound::Excluded(l))
+            | (Bound::Excluded(u), Bound::Included(l)) => l <= u,
+        }
+    }
+
+    /// Applies a monotonically increasing function to both bounds.
+    fn map(&self, f: impl Fn(f64) -> f64) -> Interval {
+        let map = |bound| match bound {
+            Bound::Included(v) => Bound::Included(f(v)),
+            Bound::Excluded(v) => Bound::Excluded(f(v)),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+        Interval {
+            lower: map(self.lower),
+            upper: map(self.upper),
+        }
+    }
+}
+
+/// Written in interval notation, e.g. `[0, 5)` or `(5, ∞)`.
+impl std::fmt::Display for Interval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.lower {
+            Bound::Included(v) => write!(f, "[{}, ", v)?,
+            Bound::Excluded(v) => write!(f, "({}, ", v)?,
+            Bound::Unbounded => write!(f, "(-∞, ")?,
+        }
+        match self.upper {
+            Bound::Included(v) => write!(f, "{}]", v),
+            Bound::Excluded(v) => write!(f, "{})", v),
+            Bound::Unbounded => write!(f, "∞)"),
+        }
+    }
+}
+
+impl From<&NumericRange> for Interval {
+    fn from(range: &NumericRange) -> Self {
+        Interval {
+            lower: range.lower(),
+            upper: range.upper(),
+        }
+    }
+}
+
+fn value(bound: &Bound<f64>) -> Option<f64> {
+    match bound {
+        Bound::Included(v) | Bound::Excluded(v) => Some(*v),
+        Bound::Unbounded => None,
+    }
+}
+
+/// Orders lower bounds by the first value they admit.
+fn cmp_lower(a: &Bound<f64>, b: &Bound<f64>) -> Ordering {
+    match (value(a), value(b)) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Less,
+        (Some(_), None) => Ordering::Greater,
+        (Some(x), Some(y)) => x.total_cmp(&y).then_with(|| match (a, b) {
+            (Bound::Included(_), Bound::Excluded(_)) => Ordering::Less,
+            (Bound::Excluded(_), Bound::Included(_)) => Ordering::Greater,
+            _ => Ordering::Equal,
+        }),
+    }
+}
+
+/// Orders upper bounds by the last value they admit.
+fn cmp_upper(a: &Bound<f64>, b: &Bound<f64>) -> Ordering {
+    match (value(a), value(b)) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(x), Some(y)) => x.total_cmp(&y).then_with(|| match (a, b) {
+            (Bound::Included(_), Bound::Excluded(_)) => Ordering::Greater,
+            (Bound::Excluded(_), Bound::Included(_)) => Ordering::Less,
+            _ => Ordering::Equal,
+        }),
+    }
+}
+
+fn flip(bound: Bound<f64>) -> Bound<f64> {
+    match bound {
+        Bound::Included(v) => Bound::Excluded(v),
+        Bound::Excluded(v) => Bound::Included(v),
+        Bound::Unbounded => Bound::Unbounded,
+    }
+}
+
+/// A set of real numbers as a union of intervals.
+///
+/// The intervals are kept normalised: non-empty, sorted and separated by gaps, so that
+/// overlapping and adjacent intervals are merged and equal sets have equal representations.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+impl IntervalSet {
+    pub fn new<I: IntoIterator<Item = Interval>>(intervals: I) -> Self {
+        let mut intervals = intervals
+            .into_iter()
+            .filter(|i| !i.is_empty())
+            .collect::<Vec<_>>();
+        intervals.sort_by(|a, b| cmp_lower(&a.lower, &b.lower));
+
+        let mut normalised: Vec<Interval> = Vec::with_capacity(intervals.len());
+        for interval in intervals {
+            match normalised.last_mut() {
+                Some(last) if last.connects(&interval) => {
+                    last.upper = std::cmp::max_by(last.upper, interval.upper, cmp_upper);
+                }
+                _ => normalised.push(interval),
+            }
+        }
+        IntervalSet {
+            intervals: normalised,
+        }
+    }
+
+    pub fn empty() -> Self {
+        IntervalSet::default()
+    }
+
+    pub fn all() -> Self {
+        IntervalSet::new(vec![Interval::new(Bound::Unbounded, Bound::Unbounded)])
+    }
+
+    pub fn intervals(&self) -> &[Interval] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn contains(&self, value: f64) -> bool {
+        self.intervals.iter().any(|i| i.contains(value))
+    }
+
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        IntervalSet::new(self.intervals.iter().chain(other.intervals.iter()).copied())
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        IntervalSet::new(
+            self.intervals
+                .iter()
+                .flat_map(|a| other.intervals.iter().map(move |b| a.intersection(b))),
+        )
+    }
+
+    pub fn complement(&self) -> IntervalSet {
+        let mut gaps = Vec::with_capacity(self.intervals.len() + 1);
+        let mut lower = Bound::Unbounded;
+        for interval in &self.intervals {
+            if interval.lower != Bound::Unbounded {
+                gaps.push(Interval::new(lower, flip(interval.lower)));
+            }
+            lower = flip(interval.upper);
+        }
+        match self.intervals.last() {
+            Some(Interval {
+                upper: Bound::Unbounded,
+                ..
+            }) => {}
+            _ => gaps.push(Interval::new(lower, Bound::Unbounded)),
+        }
+        IntervalSet::new(gaps)
+    }
+
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        self.intersection(&other.complement())
+    }
+
+    pub fn is_subset_of(&self, other: &IntervalSet) -> bool {
+        self.difference(other).is_empty()
+    }
+
+    /// Multiplies all bounds with a positive `factor`, e.g. to convert between units.
+    pub fn scale(&self, factor: f64) -> IntervalSet {
+        IntervalSet::new(self.intervals.iter().map(|i| i.map(|v| v * factor)))
+    }
+
+    pub fn shift(&self, offset: f64) -> IntervalSet {
+        IntervalSet::new(self.intervals.iter().map(|i| i.map(|v| v + offset)))
+    }
+}
+
+impl std::fmt::Display for IntervalSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return write!(f, "∅");
+        }
+        let intervals = self
+            .intervals
+            .iter()
+            .map(Interval::to_string)
+            .collect::<Vec<_>>();
+        write!(f, "{}", intervals.join(" ∪ "))
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(iter: I) -> Self {
+        IntervalSet::new(iter)
+    }
+}
+
+impl<'a> FromIterator<&'a NumericRange> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = &'a NumericRange>>(iter: I) -> Self {
+        IntervalSet::new(iter.into_iter().map(Interval::from))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use Bound::{Excluded, Included, Unbounded};
+
+    fn set(intervals: &[(Bound<f64>, Bound<f64>)]) -> IntervalSet {
+        intervals
+            .iter()
+            .map(|(lower, upper)| Interval::new(*lower, *upper))
+            .collect()
+    }
+
+    fn bound() -> impl Strategy<Value = Bound<f64>> {
+        prop_oneof![
+            4 => (-10i32..10).prop_map(|v| Included(f64::from(v))),
+            4 => (-10i32..10).prop_map(|v| Excluded(f64::from(v))),
+            1 => Just(Unbounded),
+        ]
+    }
+
+    fn interval_set() -> impl Strategy<Value = IntervalSet> {
+        prop::collection::vec((bound(), bound()), 0..4).prop_map(|intervals| {
+            intervals
+                .into_iter()
+                .map(|(lower, upper)| Interval::new(lower, upper))
+                .collect()
+        })
+    }
+
+    fn samples() -> impl Iterator<Item = f64> {
+        (-24..=24).map(|v| f64::from(v) / 2.0)
+    }
+
+    #[test]
+    fn test_normalise() {
+        assert_eq!(
+            set(&[
+                (Included(5.0), Included(10.0)),
+                (Included(0.0), Included(5.0))
+            ]),
+            set(&[(Included(0.0), Included(10.0))])
+        );
+        assert_eq!(
+            set(&[
+                (Included(0.0), Excluded(5.0)),
+                (Included(5.0), Included(10.0))
+            ]),
+            set(&[(Included(0.0), Included(10.0))])
+        );
+        // 5 is in neither interval
+        assert_eq!(
+            set(&[
+                (Included(0.0), Excluded(5.0)),
+                (Excluded(5.0), Included(10.0))
+            ])
+            .intervals()
+            .len(),
+            2
+        );
+        assert_eq!(
+            set(&[
+                (Included(0.0), Included(10.0)),
+                (Excluded(2.0), Excluded(3.0))
+            ]),
+            set(&[(Included(0.0), Included(10.0))])
+        );
+        assert!(set(&[
+            (Included(1.0), Excluded(1.0)),
+            (Included(2.0), Included(1.0))
+        ])
+        .is_empty());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            set(&[(Included(0.0), Excluded(2.5)), (Excluded(3.0), Unbounded)]).to_string(),
+            "[0, 2.5) ∪ (3, ∞)"
+        );
+        assert_eq!(set(&[(Unbounded, Included(-1.0))]).to_string(), "(-∞, -1]");
+        assert_eq!(IntervalSet::empty().to_string(), "∅");
+    }
+
+    #[test]
+    fn test_union_covers_range() {
+        let covered: IntervalSet = [
+            NumericRange::Inclusive(0.0..=5.0),
+            NumericRange::Inclusive(5.0..=10.0),
+        ]
+        .iter()
+        .collect();
+        let demanded: IntervalSet = [NumericRange::Inclusive(2.0..=8.0)].iter().collect();
+        assert!(demanded.is_subset_of(&covered));
+        assert!(!covered.is_subset_of(&demanded));
+    }
+
+    #[test]
+    fn test_operations() {
+        let a = set(&[(Included(0.0), Included(10.0))]);
+        let b = set(&[(Included(3.0), Excluded(5.0))]);
+        assert_eq!(
+            a.difference(&b),
+            set(&[
+                (Included(0.0), Excluded(3.0)),
+                (Included(5.0), Included(10.0))
+            ])
+        );
+        assert_eq!(a.intersection(&b), b);
+        assert_eq!(a.union(&b), a);
+        assert_eq!(
+            a.complement(),
+            set(&[(Unbounded, Excluded(0.0)), (Excluded(10.0), Unbounded)])
+        );
+        assert_eq!(IntervalSet::empty().complement(), IntervalSet::all());
+        assert!(IntervalSet::all().complement().is_empty());
+        assert_eq!(
+            b.scale(1000.0),
+            set(&[(Included(3000.0), Excluded(5000.0))])
+        );
+        assert_eq!(
+            set(&[(Included(5.0), Unbounded)]).scale(0.001),
+            set(&[(Included(0.005), Unbounded)])
+        );
+        assert_eq!(
+            b.shift(273.15),
+            set(&[(Included(276.15), Excluded(278.15))])
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn prop_normalised(s in interval_set()) {
+            for pair in s.intervals().windows(2) {
+                prop_assert!(cmp_lower(&pair[0].lower, &pair[1].lower).is_lt());
+                prop_assert!(!pair[0].connects(&pair[1]));
+            }
+            prop_assert!(s.intervals().iter().all(|i| !i.is_empty()));
+        }
+
+        #[test]
+        fn prop_operations_match_membership(a in interval_set(), b in interval_set()) {
+            let union = a.union(&b);
+            let intersection = a.intersection(&b);
+            let difference = a.difference(&b);
+            let complement = a.complement();
+            for x in samples() {
+                prop_assert_eq!(union.contains(x), a.contains(x) || b.contains(x));
+                prop_assert_eq!(intersection.contains(x), a.contains(x) && b.contains(x));
+                prop_assert_eq!(difference.contains(x), a.contains(x) && !b.contains(x));
+                prop_assert_eq!(complement.contains(x), !a.contains(x));
+            }
+        }
+
+        #[test]
+        fn prop_subset_matches_membership(a in interval_set(), b in interval_set()) {
+            if a.is_subset_of(&b) {
+                prop_assert!(samples().all(|x| !a.contains(x) || b.contains(x)));
+            } else {
+                prop_assert!(samples().any(|x| a.contains(x) && !b.contains(x)));
+            }
+        }
+
+        #[test]
+        fn prop_complement_involution(a in interval_set()) {
+            prop_assert_eq!(a.complement().complement(), a);
+        }
+    }
+}
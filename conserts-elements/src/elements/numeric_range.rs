@@ -2,48 +2,103 @@
 //
 // SPDX-License-Identifier: MIT
 
-use crate::elements::{demands::Demand, services::RequiredService};
+use crate::elements::{demands::Demand, interval_set::Interval, services::RequiredService};
 use conserts_error::{ConSertError, ParsingError};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::ops::{Range, RangeInclusive};
+use std::ops::{Bound, Range, RangeInclusive};
 
-#[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
+#[derive(Debug, Deserialize, Clone, JsonSchema, PartialEq, Serialize)]
 pub enum NumericRange {
     Exclusive(Range<f64>),
     Inclusive(RangeInclusive<f64>),
+    /// Excludes `start`, includes `end`.
+    LeftOpen(Range<f64>),
+    /// Includes `start`, excludes `end`.
+    RightOpen(Range<f64>),
 }
 
 impl NumericRange {
+    /// The range of a one-sided threshold, unbounded on its other side.
     pub fn from(
         operator: &str,
         threshold: f64,
     ) -> Result<NumericRange, ConSertError<Demand, RequiredService>> {
         match operator {
-            "<=" | "bound to" => Ok(NumericRange::Inclusive(0.0..=threshold)),
+            "<=" | "bound to" => Ok(NumericRange::Inclusive(f64::MIN..=threshold)),
             ">=" => Ok(NumericRange::Inclusive(threshold..=f64::MAX)),
-            "<" => Ok(NumericRange::Exclusive(0.0..threshold)),
+            "<" => Ok(NumericRange::RightOpen(f64::MIN..threshold)),
             ">" => Ok(NumericRange::Exclusive(threshold..f64::MAX)),
             _ => Err(ParsingError::UnsupportedOperator(operator.to_string()).into()),
         }
     }
 
+    /// The range between two thresholds, e.g. `-5 < x <= 40`.
+    pub fn between(start: f64, start_included: bool, end: f64, end_included: bool) -> Self {
+        match (start_included, end_included) {
+            (true, true) => NumericRange::Inclusive(start..=end),
+            (false, false) => NumericRange::Exclusive(start..end),
+            (false, true) => NumericRange::LeftOpen(start..end),
+            (true, false) => NumericRange::RightOpen(start..end),
+        }
+    }
+
+    /// Whether every value of this range is also a value of `other`.
+    ///
+    /// `Exclusive` ranges are open on both ends, `Inclusive` ranges closed on both ends.
+    /// `LeftOpen` and `RightOpen` ranges are open on the respective end only.
+    /// Ends at `f64::MAX` (or infinity) are treated as unbounded, so `> 5` is included in `>= 5`.
+    /// An empty range is included in every range.
     pub fn included_in(&self, other: &NumericRange) -> bool {
-        match (self, other) {
-            (NumericRange::Exclusive(_s), NumericRange::Exclusive(_o)) => unimplemented!(),
-            (NumericRange::Exclusive(_s), NumericRange::Inclusive(_o)) => unimplemented!(),
-            (NumericRange::Inclusive(_s), NumericRange::Exclusive(_o)) => unimplemented!(),
-            (NumericRange::Inclusive(s), NumericRange::Inclusive(o)) => {
-                o.contains(s.start()) && o.contains(s.end())
+        if self.is_empty() {
+            return true;
+        }
+        let lower = match (self.lower(), other.lower()) {
+            (_, Bound::Unbounded) => true,
+            (Bound::Unbounded, _) => false,
+            (Bound::Included(s), Bound::Excluded(o)) => s > o,
+            (Bound::Included(s), Bound::Included(o))
+            | (Bound::Excluded(s), Bound::Included(o))
+            | (Bound::Excluded(s), Bound::Excluded(o)) => s >= o,
+        };
+        let upper = match (self.upper(), other.upper()) {
+            (_, Bound::Unbounded) => true,
+            (Bound::Unbounded, _) => false,
+            (Bound::Included(s), Bound::Excluded(o)) => s < o,
+            (Bound::Included(s), Bound::Included(o))
+            | (Bound::Excluded(s), Bound::Included(o))
+            | (Bound::Excluded(s), Bound::Excluded(o)) => s <= o,
+        };
+        lower && upper
+    }
+
+    pub fn contains(&self, value: f64) -> bool {
+        Interval::from(self).contains(value)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        Interval::from(self).is_empty()
+    }
+
+    pub fn lower(&self) -> Bound<f64> {
+        match self {
+            NumericRange::Exclusive(r) | NumericRange::LeftOpen(r) if r.start > f64::MIN => {
+                Bound::Excluded(r.start)
             }
+            NumericRange::RightOpen(r) if r.start > f64::MIN => Bound::Included(r.start),
+            NumericRange::Inclusive(r) if *r.start() > f64::MIN => Bound::Included(*r.start()),
+            _ => Bound::Unbounded,
         }
     }
 
-    pub(crate) fn multiply(&self, f: f64) -> Self {
+    pub fn upper(&self) -> Bound<f64> {
         match self {
-            NumericRange::Exclusive(r) => NumericRange::Exclusive((r.start * f)..(r.end * f)),
-            NumericRange::Inclusive(r) => {
-                NumericRange::Inclusive(RangeInclusive::new(r.start() * f, r.end() * f))
+            NumericRange::Exclusive(r) | NumericRange::RightOpen(r) if r.end < f64::MAX => {
+                Bound::Excluded(r.end)
             }
+            NumericRange::LeftOpen(r) if r.end < f64::MAX => Bound::Included(r.end),
+            NumericRange::Inclusive(r) if *r.end() < f64::MAX => Bound::Included(*r.end()),
+            _ => Bound::Unbounded,
         }
     }
 }
@@ -51,11 +106,111 @@ impl NumericRange {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
+
+    fn start() -> impl Strategy<Value = f64> {
+        prop_oneof![
+            8 => (-20i32..20).prop_map(f64::from),
+            1 => Just(f64::MIN),
+            1 => Just(f64::NEG_INFINITY),
+        ]
+    }
+
+    fn end() -> impl Strategy<Value = f64> {
+        prop_oneof![
+            8 => (-20i32..20).prop_map(f64::from),
+            1 => Just(f64::MAX),
+            1 => Just(f64::INFINITY),
+        ]
+    }
+
+    fn range() -> impl Strategy<Value = NumericRange> {
+        (start(), any::<bool>(), end(), any::<bool>()).prop_map(
+            |(start, start_included, end, end_included)| {
+                NumericRange::between(start, start_included, end, end_included)
+            },
+        )
+    }
+
+    /// Values just around the ends and in the middle of a range with integral ends.
+    fn witnesses(range: &NumericRange) -> Vec<f64> {
+        let value = |bound, unbounded| match bound {
+            Bound::Included(v) | Bound::Excluded(v) => v,
+            Bound::Unbounded => unbounded,
+        };
+        let (start, end) = (value(range.lower(), -1e9), value(range.upper(), 1e9));
+        vec![start, start + 0.5, (start + end) / 2.0, end - 0.5, end]
+    }
+
+    #[test]
+    fn test_included_in() {
+        let closed = NumericRange::Inclusive(0.0..=5.0);
+        let open = NumericRange::Exclusive(0.0..5.0);
+        assert!(open.included_in(&closed));
+        assert!(!closed.included_in(&open));
+        assert!(open.included_in(&open));
+        assert!(NumericRange::Inclusive(1.0..=4.0).included_in(&open));
+        assert!(!NumericRange::Inclusive(0.0..=4.0).included_in(&open));
+        assert!(NumericRange::Exclusive(0.0..6.0).included_in(&NumericRange::Inclusive(0.0..=6.0)));
+        assert!(!NumericRange::Exclusive(0.0..6.0).included_in(&closed));
+
+        // Unbounded ends
+        let at_least = NumericRange::from(">=", 5.0).unwrap();
+        let above = NumericRange::from(">", 5.0).unwrap();
+        assert!(above.included_in(&at_least));
+        assert!(!at_least.included_in(&above));
+        assert!(NumericRange::from(">=", 6.0).unwrap().included_in(&above));
+        assert!(NumericRange::Inclusive(6.0..=f64::INFINITY).included_in(&above));
+        assert!(!above.included_in(&NumericRange::Inclusive(5.0..=100.0)));
+
+        // Half-open ranges
+        let left_open = NumericRange::between(0.0, false, 5.0, true);
+        let right_open = NumericRange::between(0.0, true, 5.0, false);
+        assert!(open.included_in(&left_open) && left_open.included_in(&closed));
+        assert!(open.included_in(&right_open) && right_open.included_in(&closed));
+        assert!(!left_open.included_in(&right_open));
+        assert!(left_open.contains(5.0) && !left_open.contains(0.0));
+        assert!(right_open.contains(0.0) && !right_open.contains(5.0));
+
+        // Empty ranges
+        assert!(NumericRange::Exclusive(3.0..3.0).included_in(&closed));
+        assert!(NumericRange::Inclusive(7.0..=6.0).included_in(&closed));
+        assert!(NumericRange::Inclusive(3.0..=3.0).included_in(&closed));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_included_in_reflexive(r in range()) {
+            prop_assert!(r.included_in(&r));
+        }
+
+        #[test]
+        fn prop_included_in_transitive(a in range(), b in range(), c in range()) {
+            if a.included_in(&b) && b.included_in(&c) {
+                prop_assert!(a.included_in(&c));
+            }
+        }
+
+        #[test]
+        fn prop_included_in_sound(a in range(), b in range(), x in -25i32..25) {
+            let x = f64::from(x) / 2.0;
+            if a.included_in(&b) && a.contains(x) {
+                prop_assert!(b.contains(x));
+            }
+        }
+
+        #[test]
+        fn prop_included_in_complete(a in range(), b in range()) {
+            if !a.included_in(&b) {
+                prop_assert!(witnesses(&a).into_iter().any(|x| a.contains(x) && !b.contains(x)));
+            }
+        }
+    }
     #[test]
     fn test_from() {
         assert_eq!(
             NumericRange::from("<", 5.7).unwrap(),
-            NumericRange::Exclusive(0.0..5.7)
+            NumericRange::RightOpen(f64::MIN..5.7)
         );
         assert_eq!(
             NumericRange::from(">", 5.7).unwrap(),
@@ -63,12 +218,24 @@ mod tests {
         );
         assert_eq!(
             NumericRange::from("<=", 5.7).unwrap(),
-            NumericRange::Inclusive(0.0..=5.7)
+            NumericRange::Inclusive(f64::MIN..=5.7)
         );
         assert_eq!(
             NumericRange::from(">=", 5.7).unwrap(),
             NumericRange::Inclusive(5.7..=f64::MAX)
         );
+        assert_eq!(
+            NumericRange::from("<", -5.0).unwrap(),
+            NumericRange::RightOpen(f64::MIN..-5.0)
+        );
+        assert!(NumericRange::from("<=", 0.0).unwrap().contains(-1.0));
+        assert!(NumericRange::from("<", 40.0).unwrap().contains(-10.0));
+
+        // an upper threshold only excludes its end
+        let below = NumericRange::from("<", 5.0).unwrap();
+        assert!(below.contains(0.0) && !below.contains(5.0));
+        assert!(NumericRange::from("<=", 4.0).unwrap().included_in(&below));
+        assert!(!NumericRange::from("<=", 5.0).unwrap().included_in(&below));
 
         let r = NumericRange::from("foo", 5.7).unwrap_err();
         if let ConSertError::Parsing { source } = r {
@@ -4,19 +4,16 @@
 
 use crate::elements::{demands::Demand, services::RequiredService};
 use conserts_error::{ConSertError, UnitOfMeasureError};
-use inflector::cases::titlecase;
+use inflector::cases::snakecase::to_snake_case;
+use schemars::gen::SchemaGenerator;
+use schemars::schema::{
+    InstanceType, Metadata, Schema, SchemaObject, StringValidation, SubschemaValidation,
+};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
 use std::convert::TryFrom;
-use std::mem::discriminant;
-
-#[derive(Debug, PartialEq, Clone, Eq, Ord, PartialOrd)]
-pub enum Dimension {
-    Unitless,
-    Force,
-    Time,
-    Length,
-    Velocity,
-}
+use std::sync::{Arc, OnceLock};
 
 #[derive(Debug, PartialEq, Clone, Eq, Ord, PartialOrd)]
 pub struct Unit {
@@ -25,116 +22,494 @@ pub struct Unit {
     pub plural: String,
 }
 
-/// The encapsulated string represents the order of magnitude in the usual SI prefixes (milli, kilo, ...)
+/// A quantity that is not part of `uom::si`, declared by a project together with its units.
+#[derive(Clone, Debug, Deserialize, JsonSchema, PartialEq, PartialOrd, Serialize)]
+pub struct CustomQuantity {
+    /// Used as type name in generated code, e.g. `DetectionRate`.
+    pub name: String,
+    pub units: Vec<CustomUnit>,
+}
+
+#[derive(Clone, Debug, Deserialize, JsonSchema, PartialEq, PartialOrd, Serialize)]
+pub struct CustomUnit {
+    /// Used as identifier in generated code, e.g. `detection_per_frame`. The singular is the
+    /// name with spaces instead of underscores.
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub abbreviation: Option<String>,
+    /// Defaults to the singular with an appended "s".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plural: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    /// The value of one unit in the base unit of the quantity.
+    pub factor: f64,
+}
+
+impl CustomUnit {
+    fn unit(&self) -> Unit {
+        let singular = self.name.replace('_', " ");
+        Unit {
+            abbreviation: self
+                .abbreviation
+                .clone()
+                .unwrap_or_else(|| self.name.clone()),
+            plural: self
+                .plural
+                .clone()
+                .unwrap_or_else(|| format!("{}s", singular)),
+            singular,
+        }
+    }
+
+    fn matches(&self, value: &str) -> bool {
+        matches(
+            value,
+            &self.name,
+            &self.unit(),
+            self.aliases.iter().map(String::as_str),
+        )
+    }
+
+    fn names(&self) -> Vec<String> {
+        let unit = self.unit();
+        let mut names = vec![
+            self.name.clone(),
+            unit.abbreviation,
+            unit.singular,
+            unit.plural,
+        ];
+        names.extend(self.aliases.iter().cloned());
+        names
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, PartialOrd)]
+pub enum Quantity {
+    Si(Dimension),
+    Custom(Arc<CustomQuantity>),
+}
+
+/// A unit of a quantity in `uom::si` or of a custom quantity, together with its conversion to
+/// the base unit of that quantity: `base = (value + offset) * conversion_factor`.
 #[derive(Debug, PartialEq, Clone, PartialOrd, Serialize, Deserialize)]
 #[serde(into = "String", try_from = "String")]
 pub struct UnitOfMeasure {
-    dimension: Dimension,
+    quantity: Quantity,
+    name: String,
     unit: Unit,
     conversion_factor: f64,
+    offset: f64,
 }
 
-macro_rules! unit {
-    (  $x:path ) => {{
-        let (abbreviation, singular, plural) = {
-            use uom::si::Unit;
-            use $x as base;
-            (
-                base::abbreviation().to_string(),
-                base::singular().to_string(),
-                base::plural().to_string(),
-            )
-        };
-        Unit {
-            abbreviation,
-            singular,
-            plural,
+/// An entry of the unit registry.
+struct Entry {
+    dimension: Dimension,
+    /// The name of the unit's module in `uom::si`, e.g. `kilometer_per_hour`.
+    name: &'static str,
+    unit: Unit,
+    aliases: &'static [&'static str],
+    coefficient: f64,
+    constant: f64,
+}
+
+/// Abbreviations and aliases are case-sensitive (`mm` is not `Mm`), names are not.
+fn matches<'a>(
+    value: &str,
+    name: &str,
+    unit: &Unit,
+    mut aliases: impl Iterator<Item = &'a str>,
+) -> bool {
+    value == unit.abbreviation
+        || aliases.any(|alias| alias == value)
+        || [name, &unit.singular, &unit.plural]
+            .iter()
+            .any(|name| !name.is_empty() && name.eq_ignore_ascii_case(value))
+}
+
+impl Entry {
+    fn matches(&self, value: &str) -> bool {
+        matches(value, self.name, &self.unit, self.aliases.iter().copied())
+    }
+
+    fn names(&self) -> Vec<&str> {
+        let mut names = vec![
+            self.name,
+            &self.unit.abbreviation,
+            &self.unit.singular,
+            &self.unit.plural,
+        ];
+        names.extend(self.aliases);
+        names
+    }
+}
+
+macro_rules! entry {
+    ( $dimension:ident, $quantity:ident :: $unit:ident, $aliases:expr ) => {{
+        use uom::si::$quantity::$unit as base;
+        use uom::si::Unit as _;
+        Entry {
+            dimension: Dimension::$dimension,
+            name: stringify!($unit),
+            unit: Unit {
+                abbreviation: base::abbreviation().to_string(),
+                singular: base::singular().to_string(),
+                plural: base::plural().to_string(),
+            },
+            aliases: $aliases,
+            coefficient: <base as uom::Conversion<f64>>::coefficient(),
+            constant: <base as uom::Conversion<f64>>::constant(uom::ConstantOp::Add),
         }
     }};
 }
 
+macro_rules! units {
+    ( $( $dimension:ident ( $quantity:ident ) { $( $unit:ident $( | $alias:literal )* ; )* } )* ) => {
+        /// The quantities of `uom::si` that units can be taken from, named like their types.
+        #[derive(Debug, PartialEq, Clone, Copy, Eq, Ord, PartialOrd)]
+        pub enum Dimension {
+            $( $dimension, )*
+        }
+
+        impl Dimension {
+            /// The module of the quantity in `uom::si`, e.g. `angular_velocity`.
+            pub fn module(&self) -> &'static str {
+                match self {
+                    $( Dimension::$dimension => stringify!($quantity), )*
+                }
+            }
+
+            /// The type of the quantity in `uom::si::f64`, e.g. `AngularVelocity`.
+            pub fn type_name(&self) -> &'static str {
+                match self {
+                    $( Dimension::$dimension => stringify!($dimension), )*
+                }
+            }
+        }
+
+        fn registry() -> &'static [Entry] {
+            static REGISTRY: OnceLock<Vec<Entry>> = OnceLock::new();
+            REGISTRY.get_or_init(|| {
+                vec![ $( $( entry!($dimension, $quantity::$unit, &[ $( $alias ),* ]), )* )* ]
+            })
+        }
+    };
+}
+
+// Units are looked up by their module name, abbreviation, singular, plural or one of the
+// aliases. Where names clash, the first entry wins.
+units! {
+    Ratio(ratio) {
+        ratio;
+        percent | "pct";
+        per_mille;
+        part_per_million;
+    }
+    Length(length) {
+        kilometer;
+        meter;
+        centimeter;
+        millimeter;
+        micrometer | "um";
+        nanometer;
+        mile;
+        foot | "ft";
+        inch;
+    }
+    Time(time) {
+        day;
+        hour;
+        minute;
+        second | "sec";
+        millisecond;
+        microsecond | "us";
+        nanosecond;
+    }
+    Velocity(velocity) {
+        kilometer_per_hour | "kph";
+        meter_per_second;
+        millimeter_per_second | "mm/s";
+        mile_per_hour | "mph";
+        knot;
+    }
+    Acceleration(acceleration) {
+        meter_per_second_squared | "m/s^2" | "m/s2";
+        millimeter_per_second_squared | "mm/s^2" | "mm/s2";
+    }
+    Force(force) {
+        kilonewton;
+        newton;
+        millinewton;
+    }
+    Angle(angle) {
+        revolution;
+        radian;
+        degree | "deg";
+    }
+    AngularVelocity(angular_velocity) {
+        radian_per_second;
+        degree_per_second | "deg/s";
+        revolution_per_second | "rps";
+        revolution_per_minute;
+    }
+    Frequency(frequency) {
+        gigahertz;
+        megahertz;
+        kilohertz;
+        hertz;
+        millihertz;
+    }
+    ThermodynamicTemperature(thermodynamic_temperature) {
+        kelvin;
+        millikelvin;
+        degree_celsius | "degC" | "celsius";
+        degree_fahrenheit | "degF" | "fahrenheit";
+    }
+    Pressure(pressure) {
+        megapascal;
+        kilopascal;
+        hectopascal;
+        pascal;
+        bar;
+        millibar | "mbar";
+        atmosphere;
+        psi;
+    }
+    Mass(mass) {
+        ton | "tonne";
+        kilogram;
+        gram;
+        milligram;
+        pound;
+    }
+    ElectricPotential(electric_potential) {
+        kilovolt;
+        volt;
+        millivolt;
+        microvolt | "uV";
+    }
+    ElectricCurrent(electric_current) {
+        ampere | "amp";
+        milliampere;
+        microampere | "uA";
+    }
+    InformationRate(information_rate) {
+        gigabit_per_second | "Gbps";
+        megabit_per_second | "Mbps";
+        kilobit_per_second | "kbps";
+        bit_per_second | "bps";
+        gigabyte_per_second;
+        megabyte_per_second;
+        kilobyte_per_second;
+        byte_per_second;
+    }
+}
+
+fn lookup(value: &str) -> Option<&'static Entry> {
+    registry().iter().find(|entry| entry.matches(value))
+}
+
+/// The custom quantities declared by a model or a project. Their units are known to
+/// `UnitOfMeasure::new` and to deserialization only while they are in [`Quantities::scope`], so
+/// models declaring a quantity differently can be read one after the other.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Quantities(Vec<Arc<CustomQuantity>>);
+
+thread_local! {
+    static SCOPE: RefCell<Quantities> = RefCell::new(Quantities::default());
+}
+
+/// Restores the outer scope when dropped, also if the scoped function panics.
+struct OuterScope(Quantities);
+
+impl Drop for OuterScope {
+    fn drop(&mut self) {
+        let outer = std::mem::take(&mut self.0);
+        let _ = SCOPE.with(|scope| scope.replace(outer));
+    }
+}
+
+impl Quantities {
+    /// The quantities in scope on this thread.
+    pub fn current() -> Self {
+        SCOPE.with(|scope| scope.borrow().clone())
+    }
+
+    /// Makes the units of the quantities known while `f` runs on this thread.
+    pub fn scope<T>(&self, f: impl FnOnce() -> T) -> T {
+        let _outer = OuterScope(SCOPE.with(|scope| scope.replace(self.clone())));
+        f()
+    }
+
+    fn lookup(&self, value: &str) -> Option<(Arc<CustomQuantity>, CustomUnit)> {
+        self.0.iter().find_map(|quantity| {
+            let unit = quantity.units.iter().find(|unit| unit.matches(value))?;
+            Some((quantity.clone(), unit.clone()))
+        })
+    }
+
+    /// Adds a quantity, so that its units can be used like the units of `uom::si`. Declaring
+    /// the same quantity again has no effect.
+    pub fn declare(&mut self, quantity: CustomQuantity) -> Result<(), UnitOfMeasureError> {
+        let invalid = |message: String| UnitOfMeasureError::InvalidDeclaration {
+            quantity: quantity.name.clone(),
+            message,
+        };
+        let mut name = quantity.name.chars();
+        if !(name.next().is_some_and(|c| c.is_ascii_uppercase())
+            && name.all(|c| c.is_ascii_alphanumeric()))
+        {
+            return Err(invalid("the name has to be in PascalCase".to_string()));
+        }
+        if registry()
+            .iter()
+            .any(|entry| entry.dimension.type_name() == quantity.name)
+        {
+            return Err(invalid("it is a quantity of uom::si".to_string()));
+        }
+        if quantity.units.is_empty() {
+            return Err(invalid("it has no units".to_string()));
+        }
+        for unit in quantity.units.iter() {
+            let mut name = unit.name.chars();
+            if !(name.next().is_some_and(|c| c.is_ascii_lowercase())
+                && name.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_'))
+            {
+                return Err(invalid(format!(
+                    "the unit name {} has to be in snake_case",
+                    unit.name
+                )));
+            }
+            if !(unit.factor.is_finite() && unit.factor > 0.0) {
+                return Err(invalid(format!(
+                    "the unit {} needs a positive factor",
+                    unit.name
+                )));
+            }
+        }
+
+        if let Some(declared) = self.0.iter().find(|q| q.name == quantity.name) {
+            return match declared.as_ref() == &quantity {
+                true => Ok(()),
+                false => Err(invalid("it is declared differently elsewhere".to_string())),
+            };
+        }
+        for (index, unit) in quantity.units.iter().enumerate() {
+            let others = quantity
+                .units
+                .iter()
+                .enumerate()
+                .filter(|(other, _)| *other != index)
+                .map(|(_, other)| other)
+                .chain(self.0.iter().flat_map(|q| q.units.iter()))
+                .collect::<Vec<_>>();
+            if let Some(name) = unit.names().into_iter().find(|name| {
+                lookup(name).is_some() || others.iter().any(|other| other.matches(name))
+            }) {
+                return Err(UnitOfMeasureError::Conflict {
+                    quantity: quantity.name.clone(),
+                    unit: name,
+                });
+            }
+        }
+        self.0.push(Arc::new(quantity));
+        Ok(())
+    }
+}
+
+/// All strings that are accepted as a unit of `uom::si`.
+pub fn unit_names() -> Vec<&'static str> {
+    let mut names: Vec<&str> = vec![];
+    for name in registry().iter().flat_map(Entry::names) {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
 impl From<UnitOfMeasure> for String {
     #[cfg(not(tarpaulin_include))] // trivial
     fn from(s: UnitOfMeasure) -> Self {
-        s.quantity()
+        s.name
     }
 }
 
 impl TryFrom<String> for UnitOfMeasure {
-    type Error = ConSertError<Demand, RequiredService>;
+    type Error = UnitOfMeasureError;
 
     #[cfg(not(tarpaulin_include))] // trivial
     fn try_from(value: String) -> Result<Self, Self::Error> {
-        let (dimension, unit, conversion_factor) = match value.as_ref() {
-            "" => (Dimension::Unitless, unit!(uom::si::ratio::ratio), 1.0),
-            // Length
-            "kilometer" | "km" => (
-                Dimension::Length,
-                unit!(uom::si::length::kilometer),
-                <uom::si::length::kilometer as uom::Conversion<f64>>::coefficient(),
-            ),
-            "meter" | "m" => (
-                Dimension::Length,
-                unit!(uom::si::length::meter),
-                <uom::si::length::meter as uom::Conversion<f64>>::coefficient(),
-            ),
-            "millimeter" | "mm" => (
-                Dimension::Length,
-                unit!(uom::si::length::millimeter),
-                <uom::si::length::millimeter as uom::Conversion<f64>>::coefficient(),
-            ),
-            // Time
-            "second" | "s" => (
-                Dimension::Time,
-                unit!(uom::si::time::second),
-                <uom::si::time::second as uom::Conversion<f64>>::coefficient(),
-            ),
-            "millisecond" | "ms" => (
-                Dimension::Time,
-                unit!(uom::si::time::millisecond),
-                <uom::si::time::millisecond as uom::Conversion<f64>>::coefficient(),
-            ),
-            "microsecond" | "us" => (
-                Dimension::Time,
-                unit!(uom::si::time::microsecond),
-                <uom::si::time::microsecond as uom::Conversion<f64>>::coefficient(),
-            ),
-            "nanosecond" | "ns" => (
-                Dimension::Time,
-                unit!(uom::si::time::nanosecond),
-                <uom::si::time::nanosecond as uom::Conversion<f64>>::coefficient(),
-            ),
-            // Velocity
-            "kilometer_per_hour" | "km/h" => (
-                Dimension::Velocity,
-                unit!(uom::si::velocity::kilometer_per_hour),
-                <uom::si::velocity::kilometer_per_hour as uom::Conversion<f64>>::coefficient(),
-            ),
-            "meter_per_second" | "m/s" => (
-                Dimension::Velocity,
-                unit!(uom::si::velocity::meter_per_second),
-                <uom::si::velocity::meter_per_second as uom::Conversion<f64>>::coefficient(),
-            ),
-            // Force
-            "newton" | "N" => (
-                Dimension::Force,
-                unit!(uom::si::force::newton),
-                <uom::si::force::newton as uom::Conversion<f64>>::coefficient(),
-            ),
-            // Unsupported
-            _ => return Err(UnitOfMeasureError::UnsupportedUnit(value.to_string()).into()),
+        if let Some(entry) = lookup(&value) {
+            Ok(Self {
+                quantity: Quantity::Si(entry.dimension),
+                name: entry.name.to_string(),
+                unit: entry.unit.clone(),
+                conversion_factor: entry.coefficient,
+                offset: entry.constant,
+            })
+        } else if let Some((quantity, unit)) = SCOPE.with(|scope| scope.borrow().lookup(&value)) {
+            Ok(Self {
+                quantity: Quantity::Custom(quantity),
+                name: unit.name.clone(),
+                unit: unit.unit(),
+                conversion_factor: unit.factor,
+                offset: 0.0,
+            })
+        } else {
+            Err(UnitOfMeasureError::UnsupportedUnit(value))
+        }
+    }
+}
+
+impl JsonSchema for UnitOfMeasure {
+    #[cfg(not(tarpaulin_include))] // trivial
+    fn schema_name() -> String {
+        "UnitOfMeasure".to_string()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        let si = SchemaObject {
+            instance_type: Some(InstanceType::String.into()),
+            enum_values: Some(unit_names().into_iter().map(|name| name.into()).collect()),
+            ..Default::default()
         };
-        Ok(Self {
-            dimension,
-            unit,
-            conversion_factor,
-        })
+        let custom = SchemaObject {
+            metadata: Some(Box::new(Metadata {
+                description: Some(
+                    "The name of a unit of a quantity declared in the quantities of the model"
+                        .to_string(),
+                ),
+                ..Default::default()
+            })),
+            instance_type: Some(InstanceType::String.into()),
+            string: Some(Box::new(StringValidation {
+                pattern: Some("^[a-z][a-z0-9_]*$".to_string()),
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+        SchemaObject {
+            metadata: Some(Box::new(Metadata {
+                description: Some(
+                    "A unit of uom::si or of a quantity declared in the quantities of the model"
+                        .to_string(),
+                ),
+                ..Default::default()
+            })),
+            subschemas: Some(Box::new(SubschemaValidation {
+                any_of: Some(vec![si.into(), custom.into()]),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+        .into()
     }
 }
 
 impl UnitOfMeasure {
     pub fn new(uom: &str) -> Result<Self, ConSertError<Demand, RequiredService>> {
-        TryFrom::try_from(uom.to_string())
+        Ok(TryFrom::try_from(uom.to_string())?)
     }
     pub fn get_unit_ab(&self) -> &str {
         &self.unit.abbreviation
@@ -147,41 +522,298 @@ impl UnitOfMeasure {
     pub fn get_unit_plural(&self) -> &str {
         &self.unit.plural
     }
+
+    /// The declaration of the quantity, `None` for quantities of `uom::si`.
+    pub fn custom_quantity(&self) -> Option<&CustomQuantity> {
+        match &self.quantity {
+            Quantity::Si(_) => None,
+            Quantity::Custom(quantity) => Some(quantity),
+        }
+    }
+
     #[cfg(not(tarpaulin_include))] // trivial
     pub fn compatible(
         &self,
         other: &UnitOfMeasure,
     ) -> Result<bool, ConSertError<Demand, RequiredService>> {
-        if discriminant(&self.dimension) == discriminant(&other.dimension) {
+        let same = match (&self.quantity, &other.quantity) {
+            (Quantity::Si(dimension), Quantity::Si(o_dimension)) => dimension == o_dimension,
+            (Quantity::Custom(quantity), Quantity::Custom(o_quantity)) => {
+                quantity.name == o_quantity.name
+            }
+            _ => false,
+        };
+        if same {
             Ok(true)
         } else {
             Err(UnitOfMeasureError::Incompatible.into())
         }
     }
 
+    /// The module of the quantity, e.g. `velocity` in `uom::si` or `detection_rate`.
     #[cfg(not(tarpaulin_include))] // trivial
     pub fn quantity(&self) -> String {
-        match self.dimension {
-            Dimension::Unitless => uom::si::ratio::description().to_string(),
-            Dimension::Force => uom::si::force::description().to_string(),
-            Dimension::Length => uom::si::length::description().to_string(),
-            Dimension::Time => uom::si::time::description().to_string(),
-            Dimension::Velocity => uom::si::velocity::description().to_string(),
+        match &self.quantity {
+            Quantity::Si(dimension) => dimension.module().to_string(),
+            Quantity::Custom(quantity) => to_snake_case(&quantity.name),
         }
     }
 
+    /// The type of the quantity, e.g. `Velocity` in `uom::si::f64` or `DetectionRate`.
     #[allow(non_snake_case)]
     #[cfg(not(tarpaulin_include))] // trivial
     pub fn Quantity(&self) -> String {
-        titlecase::to_title_case(&self.quantity())
+        match &self.quantity {
+            Quantity::Si(dimension) => dimension.type_name().to_string(),
+            Quantity::Custom(quantity) => quantity.name.clone(),
+        }
     }
 
+    /// The module of the unit in the module of the quantity, e.g. `kilometer_per_hour`.
     #[cfg(not(tarpaulin_include))] // trivial
     pub fn measurement_unit(&self) -> String {
-        self.get_unit_singular().to_string()
+        self.name.clone()
+    }
+    /// Converts a value in this unit to a compatible unit.
+    pub fn convert(&self, value: f64, unit: &UnitOfMeasure) -> f64 {
+        if self == unit {
+            // avoid rounding errors of converting forth and back
+            return value;
+        }
+        (value + self.offset) * self.conversion_factor / unit.conversion_factor - unit.offset
     }
     #[cfg(not(tarpaulin_include))] // trivial
     pub(crate) fn factor(&self) -> f64 {
         self.conversion_factor
     }
+    #[cfg(not(tarpaulin_include))] // trivial
+    pub(crate) fn offset(&self) -> f64 {
+        self.offset
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_lookup() {
+        for (text, quantity, unit) in [
+            ("km", "length", "kilometer"),
+            ("meters", "length", "meter"),
+            ("um", "length", "micrometer"),
+            ("µm", "length", "micrometer"),
+            ("km/h", "velocity", "kilometer_per_hour"),
+            ("kilometers per hour", "velocity", "kilometer_per_hour"),
+            ("m/s²", "acceleration", "meter_per_second_squared"),
+            ("m/s^2", "acceleration", "meter_per_second_squared"),
+            ("rad/s", "angular_velocity", "radian_per_second"),
+            ("rpm", "angular_velocity", "revolution_per_minute"),
+            ("°", "angle", "degree"),
+            ("MHz", "frequency", "megahertz"),
+            ("°C", "thermodynamic_temperature", "degree_celsius"),
+            (
+                "degrees Celsius",
+                "thermodynamic_temperature",
+                "degree_celsius",
+            ),
+            ("kPa", "pressure", "kilopascal"),
+            ("kg", "mass", "kilogram"),
+            ("mV", "electric_potential", "millivolt"),
+            ("mA", "electric_current", "milliampere"),
+            ("Mbps", "information_rate", "megabit_per_second"),
+            ("%", "ratio", "percent"),
+            ("", "ratio", "ratio"),
+            ("minutes", "time", "minute"),
+        ]
+        .iter()
+        .copied()
+        {
+            match UnitOfMeasure::new(text) {
+                Ok(uom) => {
+                    assert_eq!(
+                        (uom.quantity(), uom.measurement_unit()),
+                        (quantity.to_string(), unit.to_string()),
+                        "{}",
+                        text
+                    );
+                }
+                Err(e) => panic!("{}: {}", text, e),
+            }
+        }
+        assert!(UnitOfMeasure::new("parsec").is_err());
+        assert!(UnitOfMeasure::new("MM").is_err());
+        assert!(UnitOfMeasure::new("KM").is_err());
+    }
+
+    #[test]
+    fn test_registry_unambiguous() {
+        // every unit can be referred to by its module name and its abbreviation
+        for entry in registry() {
+            assert_eq!(lookup(entry.name).map(|e| e.name), Some(entry.name));
+            assert_eq!(
+                lookup(&entry.unit.abbreviation).map(|e| e.name),
+                Some(entry.name)
+            );
+        }
+    }
+
+    #[test]
+    fn test_serialization_roundtrip() {
+        for name in unit_names() {
+            let uom = UnitOfMeasure::new(name).unwrap();
+            let serialized: String = uom.clone().into();
+            assert_eq!(UnitOfMeasure::new(&serialized).unwrap(), uom);
+        }
+    }
+
+    #[test]
+    fn test_conversion() {
+        let kmh = UnitOfMeasure::new("km/h").unwrap();
+        assert!((kmh.factor() - 1.0 / 3.6).abs() < 1e-12);
+        assert_eq!(kmh.offset(), 0.0);
+        let celsius = UnitOfMeasure::new("°C").unwrap();
+        assert_eq!((celsius.factor(), celsius.offset()), (1.0, 273.15));
+        assert_eq!(celsius.Quantity(), "ThermodynamicTemperature");
+        assert!(celsius
+            .compatible(&UnitOfMeasure::new("K").unwrap())
+            .is_ok());
+        assert!(celsius.compatible(&kmh).is_err());
+        let kelvin = UnitOfMeasure::new("K").unwrap();
+        assert!((celsius.convert(20.0, &kelvin) - 293.15).abs() < 1e-9);
+        assert!((kelvin.convert(293.15, &celsius) - 20.0).abs() < 1e-9);
+        let ms = UnitOfMeasure::new("m/s").unwrap();
+        assert!((kmh.convert(36.0, &ms) - 10.0).abs() < 1e-9);
+        assert_eq!(kmh.convert(40.0, &kmh), 40.0);
+    }
+
+    fn unit(name: &str, abbreviation: &str, factor: f64) -> CustomUnit {
+        CustomUnit {
+            name: name.to_string(),
+            abbreviation: Some(abbreviation.to_string()),
+            plural: None,
+            aliases: vec![],
+            factor,
+        }
+    }
+
+    #[test]
+    fn test_declare() {
+        let illuminance = CustomQuantity {
+            name: "Illuminance".to_string(),
+            units: vec![unit("lux", "lx", 1.0), unit("kilolux", "klx", 1000.0)],
+        };
+        let mut quantities = Quantities::default();
+        quantities.declare(illuminance.clone()).unwrap();
+        // declaring it again is fine, declaring it differently is not
+        quantities.declare(illuminance.clone()).unwrap();
+        let redeclared = CustomQuantity {
+            units: vec![unit("lux", "lx", 2.0)],
+            ..illuminance
+        };
+        assert!(matches!(
+            quantities.clone().declare(redeclared.clone()),
+            Err(UnitOfMeasureError::InvalidDeclaration { .. })
+        ));
+
+        let (lux, kilolux) = quantities.scope(|| {
+            (
+                UnitOfMeasure::new("Lux").unwrap(),
+                UnitOfMeasure::new("klx").unwrap(),
+            )
+        });
+        assert_eq!(lux.get_unit_singular(), "lux");
+        assert_eq!(
+            (
+                kilolux.quantity(),
+                kilolux.Quantity(),
+                kilolux.measurement_unit()
+            ),
+            (
+                "illuminance".to_string(),
+                "Illuminance".to_string(),
+                "kilolux".to_string()
+            )
+        );
+        assert_eq!(kilolux.factor(), 1000.0);
+        assert!(lux.compatible(&kilolux).is_ok());
+        assert!(lux.compatible(&UnitOfMeasure::new("m").unwrap()).is_err());
+        let serialized: String = kilolux.clone().into();
+        assert_eq!(
+            quantities.scope(|| UnitOfMeasure::new(&serialized).unwrap()),
+            kilolux
+        );
+
+        // the units are only known in the scope, where another declaration can take over
+        assert!(UnitOfMeasure::new("lx").is_err());
+        let mut other = Quantities::default();
+        other.declare(redeclared).unwrap();
+        let lux = other.scope(|| {
+            assert_eq!(Quantities::current(), other);
+            quantities.scope(|| assert!(UnitOfMeasure::new("klx").is_ok()));
+            UnitOfMeasure::new("lx").unwrap()
+        });
+        assert_eq!(lux.factor(), 2.0);
+        assert_eq!(Quantities::current(), Quantities::default());
+
+        // a panic within the scope restores the outer scope as well
+        let result =
+            std::panic::catch_unwind(|| other.scope(|| quantities.scope(|| panic!("in scope"))));
+        assert!(result.is_err());
+        assert_eq!(Quantities::current(), Quantities::default());
+    }
+
+    #[test]
+    fn test_declare_invalid() {
+        let mut quantities = Quantities::default();
+        for invalid in [
+            CustomQuantity {
+                name: "detection rate".to_string(),
+                units: vec![unit("detection", "det", 1.0)],
+            },
+            CustomQuantity {
+                name: "Length".to_string(),
+                units: vec![unit("detection", "det", 1.0)],
+            },
+            CustomQuantity {
+                name: "DetectionRate".to_string(),
+                units: vec![],
+            },
+            CustomQuantity {
+                name: "DetectionRate".to_string(),
+                units: vec![unit("Detection", "det", 1.0)],
+            },
+            CustomQuantity {
+                name: "DetectionRate".to_string(),
+                units: vec![unit("detection", "det", 0.0)],
+            },
+        ] {
+            assert!(matches!(
+                quantities.declare(invalid),
+                Err(UnitOfMeasureError::InvalidDeclaration { .. })
+            ));
+        }
+        assert_eq!(
+            quantities.declare(CustomQuantity {
+                name: "Distance".to_string(),
+                units: vec![unit("meter", "mtr", 1.0)],
+            }),
+            Err(UnitOfMeasureError::Conflict {
+                quantity: "Distance".to_string(),
+                unit: "meter".to_string()
+            })
+        );
+        assert_eq!(
+            quantities.declare(CustomQuantity {
+                name: "Points".to_string(),
+                units: vec![unit("point", "pt", 1.0), unit("kilopoint", "pt", 1000.0)],
+            }),
+            Err(UnitOfMeasureError::Conflict {
+                quantity: "Points".to_string(),
+                unit: "pt".to_string()
+            })
+        );
+        assert_eq!(quantities, Quantities::default());
+    }
 }
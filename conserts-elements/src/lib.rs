@@ -14,8 +14,11 @@ pub mod elements;
 
 pub use elements::consert_tree;
 pub use elements::demands;
+pub use elements::evaluation;
 pub use elements::evidence;
 pub use elements::guarantees;
+pub use elements::integrity;
+pub use elements::interval_set;
 pub use elements::numeric_range;
 pub use elements::services;
 pub use elements::uom;
@@ -37,6 +37,8 @@ pub enum ParsingError {
     UnsupportedOperator(String),
     #[error("Unsupported gate: {0}")]
     UnsupportedGate(String),
+    #[error("Unsupported integrity level: {0}")]
+    UnsupportedIntegrityLevel(String),
     #[error("Could not find a {0} with index: {1}")]
     WrongIndex(String, String),
     #[error("Could not parse XML document")]
@@ -53,14 +55,88 @@ pub enum ParsingError {
     MissingEvidence(String),
     #[error("Missing a matching property {0}")]
     MissingProperty(String),
+    #[error("Level {level} is not declared for {}", .r#type)]
+    UnknownLevel { r#type: String, level: String },
+    #[error("Dimension {0} covers no values")]
+    EmptyRange(String),
+    #[error("Bands differ in type or unit: {0}")]
+    InconsistentBands(String),
+    #[error("{message} at column {column} of '{text}'")]
+    InvalidDimension {
+        text: String,
+        column: usize,
+        message: String,
+    },
     #[error("Missing element with id: {0}")]
     MissingElement(String),
+    #[error("Propagation from {from} to {to} refers to unknown element: {id}")]
+    UnknownElement {
+        id: String,
+        from: String,
+        to: String,
+    },
+    #[error("Service {service} refers to unknown element: {id}")]
+    UnknownReference { service: String, id: String },
+    #[error("Propagations form a cycle: {}", .0.join(" -> "))]
+    Cycle(Vec<String>),
     #[error("Failed to parse integer: {0}")]
     Integer(ParseIntError),
-    #[error("IDs are not unique")]
-    NonUniqueIds,
+    #[error("IDs are not unique: {}", .0.join(", "))]
+    NonUniqueIds(Vec<String>),
+    #[error("Invalid model: {0}")]
+    Syntax(String),
     #[error("Failed parsing: {0}")]
     Other(String),
+    #[error("{error} at {location}")]
+    Located {
+        location: Location,
+        error: Box<ParsingError>,
+    },
+}
+
+impl ParsingError {
+    /// Attaches a location in the model file, unless the error already has one.
+    pub fn at(self, location: Location) -> Self {
+        match self.location() {
+            Some(_) => self,
+            None => ParsingError::Located {
+                location,
+                error: Box::new(self),
+            },
+        }
+    }
+
+    pub fn location(&self) -> Option<Location> {
+        match self {
+            ParsingError::Located { location, .. } => Some(*location),
+            ParsingError::Xml(error) => Some(Location {
+                line: error.pos().row as usize,
+                column: error.pos().col as usize,
+            }),
+            _ => None,
+        }
+    }
+
+    /// The error without its location.
+    pub fn kind(&self) -> &ParsingError {
+        match self {
+            ParsingError::Located { error, .. } => error.kind(),
+            error => error,
+        }
+    }
+}
+
+/// A position in a model file, lines and columns start at 1.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl std::fmt::Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
 }
 #[non_exhaustive]
 #[derive(Error, Debug, Eq, PartialEq)]
@@ -69,6 +145,25 @@ pub enum UnitOfMeasureError {
     UnsupportedUnit(String),
     #[error("Incompatible units")] // consider showing the 2 units
     Incompatible,
+    #[error("Invalid declaration of quantity {quantity}: {message}")]
+    InvalidDeclaration { quantity: String, message: String },
+    #[error("Unit {unit} of quantity {quantity} is already defined")]
+    Conflict { quantity: String, unit: String },
+}
+
+#[non_exhaustive]
+#[derive(Error, Debug, Eq, PartialEq)]
+pub enum EvaluationError {
+    #[error("No evidence or demand with id: {0}")]
+    UnknownProperty(String),
+    #[error("Value {value} of {id} is not {expected}")]
+    WrongValue {
+        id: String,
+        value: String,
+        expected: String,
+    },
+    #[error("Level {level} of {id} is not declared")]
+    UnknownLevel { id: String, level: String },
 }
 
 #[non_exhaustive]
@@ -86,10 +181,12 @@ pub enum CompileError {
 #[derive(Error, Debug)]
 pub enum CompositionError<Demand: Debug, RequiredService: Debug> {
     #[error(
-        "Failed while composing {path} with existing SoS due to\n- unmatched demands: {unmatched_demands:#?}\n- unmatched required services: {unmatched_required_services:#?}\n\n Consider providing additional ConSerts using the --provider option"
+        "Failed while composing {path} with existing SoS due to\n{explanation}\n\n Consider providing additional ConSerts using the --provider option"
     )]
     Incompatible {
         path: String,
+        /// Why the demands and required services are unmatched, one line each.
+        explanation: String,
         unmatched_demands: Vec<Arc<Mutex<Demand>>>,
         unmatched_required_services: Vec<Arc<RequiredService>>,
     },
@@ -126,6 +223,11 @@ where
         #[from]
         source: CompositionError<Demand, RequiredService>,
     },
+    #[error("Failed evaluating a ConSert: ")]
+    Evaluation {
+        #[from]
+        source: EvaluationError,
+    },
     #[error("Unit of measurement error: ")]
     UnitOfMeasure {
         #[from]
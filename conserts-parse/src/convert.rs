@@ -0,0 +1,487 @@
+// SPDX-FileCopyrightText: 2022 Fraunhofer Institute for Experimental Software Engineering IESE
+//
+// SPDX-License-Identifier: MIT
+
+use crate::dimensions::modulo_subset;
+use crate::{Json, Xml, Yaml};
+use conserts_elements::consert::Consert;
+use conserts_elements::consert_tree::{ConsertTree, ConsertTreeElement};
+use conserts_elements::dimension::Dimension;
+use conserts_elements::{demands::Demand, services::RequiredService};
+use conserts_error::ConSertError;
+use std::fmt::Debug;
+use std::path::Path;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Format {
+    Xml,
+    Yaml,
+    Json,
+}
+
+impl Format {
+    pub fn from_path<P: AsRef<Path>>(path: &P) -> Option<Format> {
+        match path.as_ref().extension()?.to_str()? {
+            "xml" | "model" => Some(Format::Xml),
+            "yml" | "yaml" => Some(Format::Yaml),
+            "json" => Some(Format::Json),
+            _ => None,
+        }
+    }
+
+    /// The format a model in this format is converted to by default.
+    pub fn other(&self) -> Format {
+        match self {
+            Format::Xml => Format::Yaml,
+            Format::Yaml | Format::Json => Format::Xml,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Format::Xml => "model",
+            Format::Yaml => "yml",
+            Format::Json => "json",
+        }
+    }
+
+    pub fn read<P: AsRef<Path>>(
+        &self,
+        path: &P,
+    ) -> Result<Consert, ConSertError<Demand, RequiredService>> {
+        match self {
+            Format::Xml => Consert::from_path_xml(path),
+            Format::Yaml => Consert::from_path_yaml(path),
+            Format::Json => Consert::from_path_json(path),
+        }
+    }
+
+    pub fn write(
+        &self,
+        consert: &Consert,
+    ) -> Result<String, ConSertError<Demand, RequiredService>> {
+        match self {
+            Format::Xml => consert.to_xml(),
+            Format::Yaml => consert.to_yaml(),
+            Format::Json => consert.to_json(),
+        }
+    }
+
+    fn reparse(
+        &self,
+        consert: &Consert,
+        model: String,
+    ) -> Result<Consert, ConSertError<Demand, RequiredService>> {
+        let path = consert.path();
+        match self {
+            Format::Xml => {
+                let doc = crate::parse_model(&model)?;
+                Consert::from_xml(&path, consert.checksum(), doc)
+            }
+            Format::Yaml => Consert::from_yaml(&path, consert.checksum(), model),
+            Format::Json => Consert::from_json(&path, consert.checksum(), model),
+        }
+    }
+}
+
+pub struct Conversion {
+    pub model: String,
+    /// Information of the original ConSert that is not contained in the converted model.
+    pub losses: Vec<String>,
+}
+
+/// Writes the ConSert in the given format. The written model is read back and compared
+/// with the original one, every difference is reported as a loss.
+pub fn convert(
+    consert: &Consert,
+    format: Format,
+) -> Result<Conversion, ConSertError<Demand, RequiredService>> {
+    let model = format.write(consert)?;
+    let converted = format.reparse(consert, model.clone())?;
+    Ok(Conversion {
+        model,
+        losses: losses(consert, &converted),
+    })
+}
+
+fn losses(original: &Consert, converted: &Consert) -> Vec<String> {
+    #![allow(clippy::unwrap_used)]
+    let mut losses = vec![];
+
+    // the textual dimensions of XML models have no subset relationship
+    let dimensions = |dimensions: &[Dimension]| {
+        format!(
+            "{:?}",
+            dimensions.iter().map(modulo_subset).collect::<Vec<_>>()
+        )
+    };
+    let evidence = |c: &Consert| {
+        c.evidence()
+            .iter()
+            .map(|e| {
+                (
+                    e.id.clone(),
+                    vec![
+                        ("id", format!("{:?}", e.id)),
+                        ("description", format!("{:?}", e.description)),
+                        ("dimension", format!("{:?}", modulo_subset(&e.dimension))),
+                    ],
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+    compare(
+        "evidence",
+        evidence(original),
+        evidence(converted),
+        &mut losses,
+    );
+
+    let guarantees = |c: &Consert| {
+        c.guarantees()
+            .iter()
+            .map(|g| {
+                (
+                    g.id.clone(),
+                    vec![
+                        ("id", format!("{:?}", g.id)),
+                        ("description", format!("{:?}", g.description)),
+                        ("dimensions", dimensions(&g.dimensions)),
+                        ("integrity level", format!("{:?}", g.integrity)),
+                        ("tree", describe_tree(&g.cst)),
+                    ],
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+    compare(
+        "guarantee",
+        guarantees(original),
+        guarantees(converted),
+        &mut losses,
+    );
+
+    let demands = |c: &Consert| {
+        c.demands()
+            .iter()
+            .map(|d| {
+                let d = d.lock().unwrap();
+                (
+                    d.id.clone(),
+                    vec![
+                        ("id", format!("{:?}", d.id)),
+                        ("description", format!("{:?}", d.description)),
+                        ("dimensions", dimensions(&d.dimensions)),
+                        ("integrity level", format!("{:?}", d.integrity)),
+                    ],
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+    compare("demand", demands(original), demands(converted), &mut losses);
+
+    let provided_services = |c: &Consert| {
+        c.provided_services()
+            .iter()
+            .map(|p| {
+                (
+                    p.ident.clone(),
+                    vec![
+                        ("id", format!("{:?}", p.ident)),
+                        (
+                            "functional service type",
+                            format!("{:?}", p.functional_service_type),
+                        ),
+                        (
+                            "guarantees",
+                            format!(
+                                "{:?}",
+                                p.guarantees.iter().map(|g| g.index).collect::<Vec<_>>()
+                            ),
+                        ),
+                    ],
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+    compare(
+        "provided service",
+        provided_services(original),
+        provided_services(converted),
+        &mut losses,
+    );
+
+    let required_services = |c: &Consert| {
+        c.required_services()
+            .iter()
+            .map(|r| {
+                (
+                    r.ident.clone(),
+                    vec![
+                        ("id", format!("{:?}", r.ident)),
+                        (
+                            "functional service type",
+                            format!("{:?}", r.functional_service_type),
+                        ),
+                        (
+                            "demands",
+                            format!(
+                                "{:?}",
+                                r.demands
+                                    .iter()
+                                    .map(|d| d.lock().unwrap().index)
+                                    .collect::<Vec<_>>()
+                            ),
+                        ),
+                    ],
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+    compare(
+        "required service",
+        required_services(original),
+        required_services(converted),
+        &mut losses,
+    );
+
+    losses
+}
+
+/// Compares the elements of one kind by id. Elements whose id is not preserved are paired in
+/// order with the remaining converted elements, the rest is reported as missing or added.
+fn compare(
+    kind: &str,
+    original: Vec<(String, Vec<(&str, String)>)>,
+    converted: Vec<(String, Vec<(&str, String)>)>,
+    losses: &mut Vec<String>,
+) {
+    let mut pairs = vec![None; original.len()];
+    let mut unpaired = (0..converted.len()).collect::<Vec<_>>();
+    for (pair, (id, _)) in pairs.iter_mut().zip(original.iter()) {
+        if let Some(position) = unpaired.iter().position(|&index| converted[index].0.eq(id)) {
+            *pair = Some(unpaired.remove(position));
+        }
+    }
+    let mut remaining = unpaired.into_iter();
+    for pair in pairs.iter_mut().filter(|pair| pair.is_none()) {
+        *pair = remaining.next();
+    }
+
+    for ((id, fields), pair) in original.iter().zip(pairs) {
+        match pair {
+            Some(index) => {
+                let (_, converted_fields) = &converted[index];
+                for ((field, value), (_, converted_value)) in fields.iter().zip(converted_fields) {
+                    if value.ne(converted_value) {
+                        losses.push(format!(
+                            "{} {}: {} {} becomes {}",
+                            kind, id, field, value, converted_value
+                        ));
+                    }
+                }
+            }
+            None => losses.push(format!("{} {}: cannot be represented", kind, id)),
+        }
+    }
+    for index in remaining {
+        losses.push(format!(
+            "{} {}: is added by the conversion",
+            kind, converted[index].0
+        ));
+    }
+}
+
+/// Describes the structure of a ConSert tree, referring to leaves by their position.
+/// Gate names are not part of the description as they are not preserved by every format.
+fn describe_tree(cst: &ConsertTree) -> String {
+    let node = &cst.data;
+    let element = match &node.element {
+        ConsertTreeElement::RuntimeEvidence(index, _) => format!("evidence #{}", index),
+        ConsertTreeElement::Demand(index, _) => format!("demand #{}", index),
+        ConsertTreeElement::Gate(_, _, function) => format!("{:?}", function),
+        ConsertTreeElement::Tautology => "true".to_string(),
+        ConsertTreeElement::Contradiction => "false".to_string(),
+    };
+    if node.children.is_empty() {
+        element
+    } else {
+        let children = node
+            .children
+            .iter()
+            .map(describe_tree)
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("{}({})", element, children)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use conserts_elements::consert::ConsertBuilder;
+    use conserts_elements::consert_tree::Tree;
+    use conserts_elements::dimension::{Dimension, OrdinalBound, SubsetRelationship};
+    use pretty_assertions::assert_eq;
+    use std::collections::BTreeSet;
+    use std::iter::FromIterator;
+
+    #[test]
+    fn test_format() {
+        assert_eq!(Format::from_path(&"a/b.model"), Some(Format::Xml));
+        assert_eq!(Format::from_path(&"a/b.xml"), Some(Format::Xml));
+        assert_eq!(Format::from_path(&"a/b.yml"), Some(Format::Yaml));
+        assert_eq!(Format::from_path(&"a/b.json"), Some(Format::Json));
+        assert_eq!(Format::from_path(&"a/b.txt"), None);
+        assert_eq!(Format::Xml.other(), Format::Yaml);
+    }
+
+    #[test]
+    fn test_compare() {
+        let elements = |elements: &[(&str, &str)]| {
+            elements
+                .iter()
+                .map(|(id, dimension)| {
+                    (
+                        id.to_string(),
+                        vec![
+                            ("id", format!("{:?}", id)),
+                            ("dimension", dimension.to_string()),
+                        ],
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        let mut losses = vec![];
+        compare(
+            "evidence",
+            elements(&[("A", "a"), ("B", "b"), ("C", "c")]),
+            elements(&[("B", "b"), ("A", "x"), ("D", "c"), ("E", "e")]),
+            &mut losses,
+        );
+        assert_eq!(
+            losses,
+            vec![
+                "evidence A: dimension a becomes x",
+                "evidence C: id \"C\" becomes \"D\"",
+                "evidence E: is added by the conversion",
+            ]
+        );
+
+        let mut losses = vec![];
+        compare(
+            "evidence",
+            elements(&[("A", "a"), ("B", "b")]),
+            elements(&[("B", "b")]),
+            &mut losses,
+        );
+        assert_eq!(losses, vec!["evidence A: cannot be represented"]);
+    }
+
+    #[test]
+    fn test_convert_xml_to_yaml() {
+        let consert = Format::Xml
+            .read(&"../models/DEIS_DemoFollowerTruckSystem.model")
+            .unwrap();
+        let conversion = convert(&consert, Format::Yaml).unwrap();
+        assert_eq!(conversion.losses, Vec::<String>::new());
+
+        let consert = Format::Xml
+            .read(&"../models/DEIS_DemoLeaderTruckSystemIncompatible.model")
+            .unwrap();
+        let conversion = convert(&consert, Format::Yaml).unwrap();
+        assert_eq!(
+            conversion.losses,
+            vec![String::from(r#"evidence RtE3: id "RtE3" becomes "RtE3_2""#)]
+        );
+    }
+
+    #[test]
+    fn test_convert_yaml_to_xml() {
+        for model in [
+            "../models/FabOS_Scanner.yml",
+            "../models/FabOS_Scanner2.yml",
+        ] {
+            let consert = Format::Yaml.read(&model).unwrap();
+            let conversion = convert(&consert, Format::Xml).unwrap();
+            assert!(
+                conversion.losses.is_empty(),
+                "{}: {:?}",
+                model,
+                conversion.losses
+            );
+        }
+    }
+
+    #[test]
+    fn test_convert_integrity_level() {
+        let path = "../models/FabOS_Scanner.yml";
+        let model = std::fs::read_to_string(path)
+            .unwrap()
+            .replace(
+                "    description: Installation Approved\n",
+                "    description: Installation Approved\n    integrity: ASIL B\n",
+            )
+            .replace(
+                "    description: Transmission Latency <= 5ms\n",
+                "    description: Transmission Latency <= 5ms\n    integrity: SIL 2\n",
+            );
+        let consert = Consert::from_yaml(&path, "".into(), model).unwrap();
+        let conversion = convert(&consert, Format::Xml).unwrap();
+        assert!(conversion.model.contains("InstallationApproved: ASIL B"));
+        assert!(conversion
+            .model
+            .contains("TransmissionLatency between 0 ms and 5 ms: SIL 2"));
+        for loss in conversion.losses {
+            assert!(!loss.contains("integrity"), "{}", loss);
+        }
+    }
+
+    #[test]
+    fn test_convert_to_xml() {
+        let mut consert = ConsertBuilder::new().name("Test").path("Test.yml");
+        let evidence = consert.add_runtime_evidence(
+            "Approved",
+            Some("Installation approved".into()),
+            Dimension::Binary {
+                r#type: "InstallationApproved".into(),
+            },
+        );
+        let evidence = Tree::leaf(evidence.into());
+        let consert = consert
+            .add_guarantee(
+                "G",
+                None,
+                Dimension::Categorical {
+                    r#type: "SIL".into(),
+                    covered: BTreeSet::from_iter(vec!["SIL 2".into()]),
+                    subset: SubsetRelationship::Demand,
+                },
+                evidence.clone(),
+            )
+            .add_guarantee(
+                "Ordinal",
+                None,
+                Dimension::Ordinal {
+                    r#type: "ASIL".into(),
+                    levels: vec!["A".into(), "B".into()],
+                    level: "B".into(),
+                    bound: OrdinalBound::AtLeast,
+                    subset: SubsetRelationship::Guarantee,
+                },
+                evidence,
+            )
+            .build()
+            .unwrap();
+
+        let conversion = convert(&consert, Format::Xml).unwrap();
+        assert!(conversion.model.contains("&quot;SIL&quot; in {SIL 2}"));
+        assert_eq!(
+            conversion.losses,
+            vec![
+                "guarantee Ordinal: description None becomes Some(\"ASIL\")".to_string(),
+                "guarantee Ordinal: dimensions [Ordinal { type: \"ASIL\", levels: [\"A\", \"B\"], level: \"B\", bound: AtLeast, subset: Guarantee }] becomes [Binary { type: \"Asil\" }]".to_string(),
+            ]
+        );
+    }
+}
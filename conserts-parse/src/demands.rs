@@ -2,10 +2,10 @@
 //
 // SPDX-License-Identifier: MIT
 
-use crate::dimensions::text_to_dimension;
+use crate::dimensions::{text_to_dimensions, text_to_integrity_level};
 use crate::{
     elements::{demands::Demand, services::RequiredService},
-    get_refinement, GetAttribute,
+    get_description, get_refinement, GetAttribute, Locate,
 };
 use conserts_error::ConSertError;
 use std::sync::{Arc, Mutex};
@@ -18,11 +18,16 @@ pub(super) fn parse(
         .enumerate()
         .map(|(index, node)| {
             let refinement = get_refinement(&node)?;
-            Ok(Arc::new(Mutex::new(Demand::from_index_and_node(
+            let dimensions = text_to_dimensions(&refinement).at(&node)?;
+            let integrity = text_to_integrity_level(&refinement).at(&node)?;
+            Ok(Arc::new(Mutex::new(Demand {
                 index,
-                node.try_get_attribute("name")?.to_string(),
-                text_to_dimension(&refinement)?,
-            )?)))
+                id: node.try_get_attribute("name")?.to_string(),
+                description: get_description(&node, &refinement, &dimensions, integrity),
+                linked_guarantees: vec![],
+                dimensions,
+                integrity,
+            })))
         })
         .collect()
 }
@@ -41,31 +46,52 @@ mod tests {
         let model =
             &std::fs::read_to_string("../models/DEIS_DemoFollowerTruckSystem.model").unwrap();
         let doc = roxmltree::Document::parse(model).unwrap();
-        let demand = doc
-            .descendants()
-            .find(|node| node.tag_name() == roxmltree::ExpandedName::from("demands"))
-            .unwrap();
+        let demands = parse(&doc).unwrap();
+        let d = demands[0].lock().unwrap();
+        assert_eq!(d.index, 0);
+        assert_eq!(d.identifier(), "Sd1");
+        assert_eq!(
+            d.description,
+            Some("Speed Deviation is bound to <= 2 km/h".to_string())
+        );
+        assert_eq!(
+            d.dimensions,
+            vec![Dimension::Numeric {
+                r#type: "SpeedDeviationIsBoundTo".into(),
+                covered: vec![NumericRange::Inclusive(RangeInclusive::new(f64::MIN, 2.0))],
+                subset: SubsetRelationship::Guarantee,
+                uom: Some(UnitOfMeasure::new("km/h").unwrap()),
+            }]
+        );
+    }
 
-        match Demand::from_index_and_node(
-            0,
-            demand.try_get_attribute("name").unwrap().to_string(),
-            text_to_dimension(&get_refinement(&demand).unwrap()).unwrap(),
-        ) {
-            Ok(d) => {
-                assert_eq!(d.index, 0);
-                assert_eq!(d.identifier(), "D0");
-                assert_eq!(d.description, Some("SD1".to_string()));
-                assert_eq!(
-                    d.dimensions,
-                    vec![Dimension::Numeric {
-                        r#type: "SpeedDeviationIsBoundTo".into(),
-                        covered: vec![NumericRange::Inclusive(RangeInclusive::new(0.0, 2.0))],
-                        subset: SubsetRelationship::Guarantee,
-                        uom: Some(UnitOfMeasure::new("km/h").unwrap()),
-                    }]
-                )
-            }
-            Err(e) => panic!("{:#?}", e),
-        }
+    #[test]
+    fn test_parse_exported() {
+        let model = r#"<conSert>
+            <demands name="Latency" description="Transmission Latency is low">
+              <safetyProperties><refinement name="Latency &lt; 5 ms; Jitter &lt; 1 ms"/></safetyProperties>
+            </demands>
+            <demands name="Approved" description="">
+              <safetyProperties><refinement name="&quot;HSEApproved&quot;"/></safetyProperties>
+            </demands>
+        </conSert>"#;
+        let doc = roxmltree::Document::parse(model).unwrap();
+        let demands = parse(&doc).unwrap();
+        let latency = demands[0].lock().unwrap();
+        assert_eq!(latency.id, "Latency");
+        assert_eq!(
+            latency.description,
+            Some("Transmission Latency is low".to_string())
+        );
+        assert_eq!(latency.dimensions.len(), 2);
+        let approved = demands[1].lock().unwrap();
+        assert_eq!(approved.id, "Approved");
+        assert_eq!(approved.description, None);
+        assert_eq!(
+            approved.dimensions,
+            vec![Dimension::Binary {
+                r#type: "HSEApproved".into()
+            }]
+        );
     }
 }
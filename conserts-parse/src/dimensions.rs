@@ -2,85 +2,512 @@
 //
 // SPDX-License-Identifier: MIT
 
+//! Dimensions in the textual form used by safeTbox models, e.g. `Speed <= 5 m/s: ASIL B`.
+//!
+//! ```text
+//! description := dimension (";" dimension)* [":" integrity-level]
+//! dimension   := type "in" "{" category ("," category)* "}"
+//!              | band ("or" band)*
+//!              | type
+//! band        := [type] operator number unit
+//!              | [type] "bound to" number unit
+//!              | [type] "between" number [unit] "and" number unit
+//!              | number [unit] ("<" | "<=") type ("<" | "<=") number unit
+//! ```
+//!
+//! Types and categories in double quotes are taken literally and may contain any character.
+//! Text after a `:` that is no integrity level is part of the type of a binary dimension and
+//! a remark after a numeric or categorical one, which is ignored. Guarantees and demands may
+//! have several dimensions, e.g. `Time <= 2 s; "PL" in {PL d}: SIL 2`.
+
+use crate::tokens::{self, Operator, Token, TokenKind};
 use conserts_elements::dimension::SubsetRelationship;
+use conserts_elements::elements::integrity::IntegrityLevel;
 use conserts_elements::elements::services::RequiredService;
-use conserts_elements::elements::uom::UnitOfMeasure;
+use conserts_elements::elements::uom::{Quantities, UnitOfMeasure};
 use conserts_elements::numeric_range::NumericRange;
 use conserts_elements::{dimension::Dimension, elements::demands::Demand};
 use conserts_error::{ConSertError, ParsingError};
-use regex::Regex;
+use std::collections::BTreeSet;
 
 extern crate inflector;
 use inflector::Inflector;
 
-static OPERATORS: &[&str] = &["<=", ">=", "<", ">", "bound to"];
-
 pub fn text_to_dimension(text: &str) -> Result<Dimension, ConSertError<Demand, RequiredService>> {
-    let (dimension, _asil) = split_asil(text)?;
-    if is_numeric_dimension(&dimension) {
-        Ok(split_numeric_dimension(&dimension)?)
-    } else {
+    let (parser, _) = split_integrity(text)?;
+    parser.dimension()
+}
+
+/// The dimensions of a text with several dimensions separated by `;`.
+pub fn text_to_dimensions(
+    text: &str,
+) -> Result<Vec<Dimension>, ConSertError<Demand, RequiredService>> {
+    let (parser, _) = split_integrity(text)?;
+    parser.split().into_iter().map(Parser::dimension).collect()
+}
+
+/// The integrity level written after the dimension, e.g. `Speed <= 5 m/s: ASIL B`.
+pub fn text_to_integrity_level(
+    text: &str,
+) -> Result<Option<IntegrityLevel>, ConSertError<Demand, RequiredService>> {
+    let (_, integrity) = split_integrity(text)?;
+    Ok(integrity)
+}
+
+/// Splits off the integrity level after the last `:`. A suffix that is no integrity level
+/// stays part of the dimension.
+fn split_integrity(
+    text: &str,
+) -> Result<(Parser<'_>, Option<IntegrityLevel>), ConSertError<Demand, RequiredService>> {
+    let mut tokens = tokens::tokenize(text)?;
+    if let Some(colon) = tokens.iter().rposition(|t| t.kind == TokenKind::Colon) {
+        let (start, end) = (tokens[colon].start, tokens[colon].end);
+        if let Ok(integrity) = IntegrityLevel::parse(&text[end..]) {
+            tokens.truncate(colon);
+            return Ok((Parser::new(&text[..start], tokens), integrity));
+        }
+    }
+    Ok((Parser::new(text, tokens), None))
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(text: &'a str, tokens: Vec<Token>) -> Self {
+        Parser {
+            text,
+            tokens,
+            position: 0,
+        }
+    }
+
+    /// One parser for each dimension separated by `;`.
+    fn split(self) -> Vec<Self> {
+        let mut parsers = vec![];
+        let mut tokens = vec![];
+        for token in self.tokens {
+            if token.kind == TokenKind::Semicolon {
+                parsers.push(Parser::new(&self.text[..token.start], tokens));
+                tokens = vec![];
+            } else {
+                tokens.push(token);
+            }
+        }
+        parsers.push(Parser::new(self.text, tokens));
+        parsers
+    }
+
+    fn peek(&self) -> Option<&TokenKind> {
+        self.peek_at(0)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&TokenKind> {
+        self.tokens.get(self.position + offset).map(|t| &t.kind)
+    }
+
+    fn is_word_at(&self, offset: usize, word: &str) -> bool {
+        self.tokens
+            .get(self.position + offset)
+            .is_some_and(|t| t.is_word(word))
+    }
+
+    fn expected(&self, what: &str) -> ConSertError<Demand, RequiredService> {
+        let (offset, found) = match self.tokens.get(self.position) {
+            Some(token) => (token.start, token.kind.to_string()),
+            None => (self.text.trim_end().len(), "the end".to_string()),
+        };
+        tokens::error(
+            self.text,
+            offset,
+            format!("Expected {}, found {}", what, found),
+        )
+        .into()
+    }
+
+    /// The text of the tokens from `start` up to the current position.
+    fn text_from(&self, start: usize) -> String {
+        match &self.tokens[start..self.position] {
+            [Token {
+                kind: TokenKind::Quoted(text),
+                ..
+            }] => text.clone(),
+            [first, .., last] | [first @ last] => self.text[first.start..last.end].to_string(),
+            [] => String::new(),
+        }
+    }
+
+    fn type_from(&self, start: usize) -> String {
+        match &self.tokens[start..self.position] {
+            [Token {
+                kind: TokenKind::Quoted(text),
+                ..
+            }] => text.clone(),
+            _ => self.text_from(start).to_pascal_case().replace(' ', ""),
+        }
+    }
+
+    fn dimension(mut self) -> Result<Dimension, ConSertError<Demand, RequiredService>> {
+        if self.tokens.is_empty() {
+            return Err(self.expected("a dimension"));
+        }
+        let categorical = (0..self.tokens.len()).find(|&i| {
+            self.is_word_at(i, "in") && self.peek_at(i + 1) == Some(&TokenKind::OpenBrace)
+        });
+        if let Some(at) = categorical {
+            return self.categorical(at);
+        }
+        let numeric = (0..self.tokens.len()).any(|i| {
+            matches!(self.peek_at(i), Some(TokenKind::Operator(_)))
+                || self.is_between(i)
+                || self.is_bound_to(i)
+        });
+        if numeric {
+            return self.numeric();
+        }
+        self.position = self.tokens.len();
         Ok(Dimension::Binary {
-            r#type: dimension.to_pascal_case().replace(' ', ""),
+            r#type: self.type_from(0),
         })
     }
-}
 
-pub fn split_asil(
-    dimension: &str,
-) -> Result<(String, String), ConSertError<Demand, RequiredService>> {
-    let parts: Vec<&str> = dimension.split(':').collect();
-    let asil = parts.get(1).unwrap_or(&"ASIL UNKNOWN");
-    let part = parts
-        .get(0)
-        .ok_or_else(|| ParsingError::Other("No elements in parts".to_string()))?;
+    /// Whether the dimension ends here, possibly followed by a remark.
+    fn at_end(&self) -> bool {
+        matches!(self.peek(), None | Some(TokenKind::Colon))
+    }
+
+    fn is_between(&self, offset: usize) -> bool {
+        self.is_word_at(offset, "between")
+            && matches!(self.peek_at(offset + 1), Some(TokenKind::Number(_)))
+    }
+
+    fn is_bound_to(&self, offset: usize) -> bool {
+        self.is_word_at(offset, "bound")
+            && self.is_word_at(offset + 1, "to")
+            && matches!(self.peek_at(offset + 2), Some(TokenKind::Number(_)))
+    }
+
+    fn categorical(
+        &mut self,
+        at: usize,
+    ) -> Result<Dimension, ConSertError<Demand, RequiredService>> {
+        if at == 0 {
+            return Err(self.expected("a type"));
+        }
+        self.position = at;
+        let r#type = self.type_from(0);
+        self.position += 2;
+
+        let mut covered = BTreeSet::new();
+        loop {
+            let start = self.position;
+            while matches!(
+                self.peek(),
+                Some(TokenKind::Word(_) | TokenKind::Quoted(_) | TokenKind::Number(_))
+            ) {
+                self.position += 1;
+            }
+            if start == self.position {
+                return Err(self.expected("a category"));
+            }
+            let _ = covered.insert(self.text_from(start));
+            match self.peek() {
+                Some(TokenKind::Comma) => self.position += 1,
+                Some(TokenKind::CloseBrace) => {
+                    self.position += 1;
+                    break;
+                }
+                _ => return Err(self.expected("',' or '}'")),
+            }
+        }
+        if !self.at_end() {
+            return Err(self.expected("the end of the dimension"));
+        }
+        Ok(Dimension::Categorical {
+            r#type,
+            covered,
+            subset: SubsetRelationship::Guarantee,
+        })
+    }
+
+    /// Reads bands separated by `or`. Later bands may leave out the type. All bands have to
+    /// share the type and the unit.
+    fn numeric(&mut self) -> Result<Dimension, ConSertError<Demand, RequiredService>> {
+        let mut r#type: Option<String> = None;
+        let mut unit: Option<String> = None;
+        let mut covered = vec![];
+        loop {
+            let (band_type, band_unit, range) = self.band()?;
+            match (&r#type, band_type) {
+                (None, band_type) => r#type = Some(band_type.unwrap_or_default()),
+                (Some(t), Some(band_type)) if *t != band_type => {
+                    return Err(ParsingError::InconsistentBands(self.text.to_string()).into())
+                }
+                _ => {}
+            }
+            match &unit {
+                Some(u) if *u != band_unit => {
+                    return Err(ParsingError::InconsistentBands(self.text.to_string()).into())
+                }
+                Some(_) => {}
+                None => unit = Some(band_unit),
+            }
+            covered.push(range);
+
+            if self.at_end() {
+                break;
+            } else if self.is_word_at(0, "or") {
+                self.position += 1;
+            } else {
+                return Err(self.expected("'or' or the end of the dimension"));
+            }
+        }
+
+        let dimension = Dimension::Numeric {
+            r#type: r#type.unwrap_or_default(),
+            covered,
+            subset: SubsetRelationship::Guarantee,
+            uom: Some(UnitOfMeasure::new(&unit.unwrap_or_default())?),
+        };
+        dimension.check()?;
+        Ok(dimension)
+    }
+
+    fn band(
+        &mut self,
+    ) -> Result<(Option<String>, String, NumericRange), ConSertError<Demand, RequiredService>> {
+        if let Some(TokenKind::Number(_)) = self.peek() {
+            let start = self.number()?;
+            let start_unit = self.unit();
+            let start_included = self.less()?;
+            let name = self.position;
+            while !matches!(self.peek(), Some(TokenKind::Operator(_)) | None) {
+                self.position += 1;
+            }
+            if name == self.position {
+                return Err(self.expected("a type"));
+            }
+            let r#type = self.type_from(name);
+            let end_included = self.less()?;
+            let end = self.number()?;
+            let unit = self.required_unit(start_unit)?;
+            let range = NumericRange::between(start, start_included, end, end_included);
+            return Ok((Some(r#type), unit, range));
+        }
+
+        let name = self.position;
+        loop {
+            match self.peek() {
+                Some(TokenKind::Operator(_)) => break,
+                Some(_) if self.is_between(0) || self.is_bound_to(0) => break,
+                Some(_) => self.position += 1,
+                None => return Err(self.expected("an operator, 'between' or 'bound to'")),
+            }
+        }
+        let r#type = (name < self.position).then(|| self.type_from(name));
 
-    Ok((part.to_string(), asil.to_string()))
+        if let Some(TokenKind::Operator(operator)) = self.peek() {
+            let operator = *operator;
+            self.position += 1;
+            let threshold = self.number()?;
+            let unit = self.required_unit(String::new())?;
+            Ok((
+                r#type,
+                unit,
+                NumericRange::from(operator.as_str(), threshold)?,
+            ))
+        } else if self.is_between(0) {
+            self.position += 1;
+            let start = self.number()?;
+            let start_unit = self.unit();
+            if !self.is_word_at(0, "and") {
+                return Err(self.expected("'and'"));
+            }
+            self.position += 1;
+            let end = self.number()?;
+            let unit = self.required_unit(start_unit)?;
+            Ok((r#type, unit, NumericRange::between(start, true, end, true)))
+        } else {
+            self.position += 2;
+            let threshold = self.number()?;
+            let unit = self.required_unit(String::new())?;
+            Ok((r#type, unit, NumericRange::from("bound to", threshold)?))
+        }
+    }
+
+    fn number(&mut self) -> Result<f64, ConSertError<Demand, RequiredService>> {
+        match self.peek() {
+            Some(TokenKind::Number(number)) => {
+                let number = *number;
+                self.position += 1;
+                Ok(number)
+            }
+            _ => Err(self.expected("a number")),
+        }
+    }
+
+    /// `true` for `<=`, `false` for `<`.
+    fn less(&mut self) -> Result<bool, ConSertError<Demand, RequiredService>> {
+        let included = match self.peek() {
+            Some(TokenKind::Operator(Operator::Less)) => false,
+            Some(TokenKind::Operator(Operator::LessOrEqual)) => true,
+            _ => return Err(self.expected("'<' or '<='")),
+        };
+        self.position += 1;
+        Ok(included)
+    }
+
+    /// The words up to the next operator, `and`, `or` or the end.
+    fn unit(&mut self) -> String {
+        let start = self.position;
+        while matches!(self.peek(), Some(TokenKind::Word(_) | TokenKind::Quoted(_)))
+            && !self.is_word_at(0, "and")
+            && !self.is_word_at(0, "or")
+        {
+            self.position += 1;
+        }
+        self.text_from(start)
+    }
+
+    /// The unit after the last number of a band, which has to match a unit given before.
+    fn required_unit(
+        &mut self,
+        before: String,
+    ) -> Result<String, ConSertError<Demand, RequiredService>> {
+        let unit = self.unit();
+        if unit.is_empty() {
+            Err(self.expected("a unit"))
+        } else if !before.is_empty() && before != unit {
+            Err(ParsingError::InconsistentBands(self.text.to_string()).into())
+        } else {
+            Ok(unit)
+        }
+    }
 }
 
-pub fn is_numeric_dimension(text: &str) -> bool {
-    OPERATORS.iter().any(|op| text.contains(op))
+/// Writes a dimension in the textual form used by safeTbox models. Returns `None` if the
+/// dimension cannot be expressed in a way that `text_to_dimension` reads back unchanged.
+/// The textual form has no subset relationship, it is read as a subset of the guarantee.
+pub fn dimension_to_text(dimension: &Dimension) -> Option<String> {
+    // the declaration of a custom quantity comes with its unit
+    let mut quantities = Quantities::current();
+    if let Dimension::Numeric { uom: Some(uom), .. } = dimension {
+        if let Some(quantity) = uom.custom_quantity() {
+            quantities.declare(quantity.clone()).ok()?;
+        }
+    }
+    quantities.scope(|| readable_text(dimension))
 }
 
-pub fn split_numeric_dimension(
-    text: &str,
-) -> Result<Dimension, ConSertError<Demand, RequiredService>> {
-    for &op in OPERATORS {
-        if text.contains(op) {
-            let parts: Vec<&str> = text.split(op).collect();
-
-            let bound = parts[1].trim();
-
-            let re = Regex::new(r"^([\d\.,]+)[\s]*(.+)$")?;
-            let caps = re
-                .captures(bound)
-                .ok_or_else(|| ParsingError::Other("Regex did not match".to_string()))?;
-
-            let threshold = match caps.get(1) {
-                Some(t) => t.as_str(),
-                None => return Err(ParsingError::MissingThreshold(bound.to_string()).into()),
-            };
-
-            let unit = match caps.get(2) {
-                Some(u) => u.as_str(),
-                None => return Err(ParsingError::MissingUnit(bound.to_string()).into()),
-            };
-
-            let threshold = threshold.parse().map_err(|_| {
-                ParsingError::Other(format!("Could not parse threshold: {}", threshold))
-            })?;
-
-            let dimension = Dimension::Numeric {
-                r#type: parts[0].to_pascal_case().replace(' ', ""),
-                covered: vec![NumericRange::from(op, threshold)?],
-                subset: SubsetRelationship::Guarantee,
-                uom: Some(UnitOfMeasure::new(unit)?),
-            };
+/// Writes several dimensions separated by `;`, see `dimension_to_text`.
+pub fn dimensions_to_text(dimensions: &[Dimension]) -> Option<String> {
+    if dimensions.is_empty() {
+        return None;
+    }
+    dimensions
+        .iter()
+        .map(dimension_to_text)
+        .collect::<Option<Vec<_>>>()
+        .map(|texts| texts.join("; "))
+}
+
+/// The dimension as it is read back from its textual form.
+pub fn modulo_subset(dimension: &Dimension) -> Dimension {
+    let mut dimension = dimension.clone();
+    match &mut dimension {
+        Dimension::Binary { .. } => {}
+        Dimension::Categorical { subset, .. }
+        | Dimension::Ordinal { subset, .. }
+        | Dimension::Numeric { subset, .. } => *subset = SubsetRelationship::Guarantee,
+    }
+    dimension
+}
 
-            return Ok(dimension);
+fn readable_text(dimension: &Dimension) -> Option<String> {
+    let text = match dimension {
+        Dimension::Binary { r#type } => type_to_text(r#type),
+        Dimension::Categorical {
+            r#type, covered, ..
+        } => format!(
+            "{} in {{{}}}",
+            type_to_text(r#type),
+            covered
+                .iter()
+                .map(
+                    |category| match category.contains(['"', ',', ';', '{', '}', ':']) {
+                        true => format!("\"{}\"", category),
+                        false => category.clone(),
+                    }
+                )
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        Dimension::Ordinal { .. } => return None,
+        Dimension::Numeric {
+            r#type,
+            covered,
+            uom,
+            ..
+        } => {
+            let r#type = type_to_text(r#type);
+            let unit = uom.clone().map(unit_to_text).unwrap_or_default();
+            let bands = covered
+                .iter()
+                .map(|range| range_to_text(&r#type, range, &unit))
+                .collect::<Vec<_>>();
+            bands.join(" or ")
         }
+    };
+    match text_to_dimension(&text) {
+        Ok(parsed) if parsed.eq(&modulo_subset(dimension)) => Some(text),
+        _ => None,
+    }
+}
+
+/// Types are read in PascalCase, other types are quoted to be read back unchanged.
+fn type_to_text(r#type: &str) -> String {
+    match r#type.to_pascal_case().eq(r#type) {
+        true => r#type.to_string(),
+        false => format!("\"{}\"", r#type),
+    }
+}
+
+fn range_to_text(r#type: &str, range: &NumericRange, unit: &str) -> String {
+    let one_sided =
+        |operator: &str, threshold: f64| format!("{} {} {} {}", r#type, operator, threshold, unit);
+    let two_sided = |start: f64, lower: &str, end: f64, upper: &str| {
+        format!(
+            "{} {} {} {} {} {} {}",
+            start, unit, lower, r#type, upper, end, unit
+        )
+    };
+    match range {
+        NumericRange::Inclusive(r) if *r.start() == f64::MIN => one_sided("<=", *r.end()),
+        NumericRange::Inclusive(r) if *r.end() == f64::MAX => one_sided(">=", *r.start()),
+        NumericRange::RightOpen(r) if r.start == f64::MIN => one_sided("<", r.end),
+        NumericRange::Exclusive(r) if r.end == f64::MAX => one_sided(">", r.start),
+        NumericRange::Inclusive(r) => format!(
+            "{} between {} {} and {} {}",
+            r#type,
+            r.start(),
+            unit,
+            r.end(),
+            unit
+        ),
+        NumericRange::Exclusive(r) => two_sided(r.start, "<", r.end, "<"),
+        NumericRange::LeftOpen(r) => two_sided(r.start, "<", r.end, "<="),
+        NumericRange::RightOpen(r) => two_sided(r.start, "<=", r.end, "<"),
+    }
+}
+
+fn unit_to_text(uom: UnitOfMeasure) -> String {
+    match UnitOfMeasure::new(uom.get_unit_ab()) {
+        Ok(parsed) if parsed.eq(&uom) => uom.get_unit_ab().to_string(),
+        _ => uom.into(),
     }
-    Err(ParsingError::UnsupportedOperator(text.to_string()).into())
 }
 
 #[cfg(test)]
@@ -108,24 +535,287 @@ mod tests {
     }
 
     #[test]
-    fn test_split_numeric_dimension_fail() {
-        let r = split_numeric_dimension("FooBar < 5").unwrap_err();
-        if let ConSertError::Parsing { source } = r {
+    fn test_two_sided_thresholds() {
+        for (text, covered) in [
+            (
+                "Speed between 10 km/h and 30 km/h",
+                NumericRange::Inclusive(10.0..=30.0),
+            ),
+            (
+                "Speed between 10 and 30 km/h",
+                NumericRange::Inclusive(10.0..=30.0),
+            ),
+            (
+                "-5 km/h < Speed < 40 km/h",
+                NumericRange::Exclusive(-5.0..40.0),
+            ),
+            ("-5 < Speed <= 40 km/h", NumericRange::LeftOpen(-5.0..40.0)),
+            (
+                "Speed >= -2,5 km/h: ASIL B",
+                NumericRange::Inclusive(-2.5..=f64::MAX),
+            ),
+            ("Speed < -2 km/h", NumericRange::RightOpen(f64::MIN..-2.0)),
+        ] {
             assert_eq!(
-                source,
-                ParsingError::Other("Regex did not match".to_string())
+                text_to_dimension(text).unwrap(),
+                Dimension::Numeric {
+                    r#type: "Speed".into(),
+                    covered: vec![covered],
+                    subset: SubsetRelationship::Guarantee,
+                    uom: Some(UnitOfMeasure::new("km/h").unwrap()),
+                },
+                "{}",
+                text
             );
-        } else {
-            panic!();
         }
-        let r = split_numeric_dimension("FooBar 5").unwrap_err();
-        if let ConSertError::Parsing { source } = r {
-            assert_eq!(
-                source,
-                ParsingError::UnsupportedOperator("FooBar 5".to_string())
-            );
-        } else {
-            panic!();
+        match text_to_dimension("T < 40 °C").unwrap() {
+            Dimension::Numeric { covered, .. } => assert!(covered[0].contains(-10.0)),
+            dimension => panic!("{:?}", dimension),
         }
     }
+
+    #[test]
+    fn test_multiple_bands() {
+        let expected = Dimension::Numeric {
+            r#type: "Speed".into(),
+            covered: vec![
+                NumericRange::RightOpen(f64::MIN..10.0),
+                NumericRange::RightOpen(20.0..30.0),
+                NumericRange::Inclusive(40.5..=f64::MAX),
+            ],
+            subset: SubsetRelationship::Guarantee,
+            uom: Some(UnitOfMeasure::new("km/h").unwrap()),
+        };
+        assert_eq!(
+            text_to_dimension("Speed < 10 km/h or 20 km/h <= Speed < 30 km/h or >= 40,5 km/h")
+                .unwrap(),
+            expected
+        );
+        assert_eq!(
+            dimension_to_text(&expected),
+            Some("Speed < 10 km/h or 20 km/h <= Speed < 30 km/h or Speed >= 40.5 km/h".to_string())
+        );
+
+        for text in &[
+            "Speed < 10 km/h or Distance > 20 km/h",
+            "Speed < 10 km/h or Speed > 20 m/s",
+            "Speed between 10 m/s and 20 km/h",
+        ] {
+            let r = text_to_dimension(text).unwrap_err();
+            if let ConSertError::Parsing { source } = r {
+                assert_eq!(source, ParsingError::InconsistentBands(text.to_string()));
+            } else {
+                panic!();
+            }
+        }
+    }
+
+    #[test]
+    fn test_text_to_integrity_level() {
+        let text = "Speed <= 5 m/s: ASIL B";
+        assert_eq!(
+            text_to_integrity_level(text).unwrap(),
+            Some(IntegrityLevel::AsilB)
+        );
+        assert_eq!(
+            text_to_dimension(text).unwrap(),
+            text_to_dimension("Speed <= 5 m/s").unwrap()
+        );
+        assert_eq!(text_to_integrity_level("Speed <= 5 m/s").unwrap(), None);
+
+        // descriptions may contain colons that are no integrity levels
+        let text = "Speed <= 5 m/s: checked by the driver";
+        assert_eq!(text_to_integrity_level(text).unwrap(), None);
+        assert_eq!(
+            text_to_dimension(text).unwrap(),
+            text_to_dimension("Speed <= 5 m/s").unwrap()
+        );
+        assert_eq!(
+            text_to_dimension("Weather in {Sunny}: see manual").unwrap(),
+            text_to_dimension("Weather in {Sunny}").unwrap()
+        );
+        assert_eq!(
+            text_to_dimension("Note: wheel speeds healthy").unwrap(),
+            Dimension::Binary {
+                r#type: "NoteWheelSpeedsHealthy".into()
+            }
+        );
+        assert_eq!(
+            text_to_integrity_level("Mode: manual: ASIL C").unwrap(),
+            Some(IntegrityLevel::AsilC)
+        );
+    }
+
+    #[test]
+    fn test_dimension_to_text() {
+        for text in [
+            "Communication Delay bound to < 300.5 ms",
+            "Speed Deviation is bound to <= 2 km/h",
+            "Distance >= 10 m",
+            "Force > 5 N",
+            "Follower Speed Determination healthy",
+            "Speed between 10 km/h and 30 km/h",
+            "-5 m < Distance <= 40,5 m or Distance > 100 m",
+            "Temperature Deviation <= -0.5 m/s",
+            "Cooling > 20 °C",
+            "Rotation between 10 rpm and 3000 rpm",
+        ] {
+            let dimension = text_to_dimension(text).unwrap();
+            let written = dimension_to_text(&dimension).unwrap();
+            assert_eq!(text_to_dimension(&written).unwrap(), dimension);
+        }
+
+        let dimension = Dimension::Numeric {
+            r#type: "Delay".into(),
+            covered: vec![NumericRange::Inclusive(1.0..=2.0)],
+            subset: SubsetRelationship::Guarantee,
+            uom: Some(UnitOfMeasure::new("us").unwrap()),
+        };
+        assert_eq!(
+            dimension_to_text(&dimension),
+            Some("Delay between 1 µs and 2 µs".to_string())
+        );
+
+        let dimension = Dimension::Numeric {
+            r#type: "Delay".into(),
+            covered: vec![NumericRange::Inclusive(0.0..=2.0)],
+            subset: SubsetRelationship::Guarantee,
+            uom: Some(UnitOfMeasure::new("us").unwrap()),
+        };
+        assert_eq!(
+            dimension_to_text(&dimension),
+            Some("Delay between 0 µs and 2 µs".to_string())
+        );
+    }
+
+    fn parsing_error(text: &str) -> ParsingError {
+        match text_to_dimension(text) {
+            Err(ConSertError::Parsing { source }) => source,
+            other => panic!("{:?}", other),
+        }
+    }
+
+    fn invalid(text: &str, column: usize, message: &str) -> ParsingError {
+        ParsingError::InvalidDimension {
+            text: text.into(),
+            column,
+            message: message.into(),
+        }
+    }
+
+    #[test]
+    fn test_text_to_dimension_fail() {
+        assert_eq!(
+            parsing_error("FooBar < 5"),
+            invalid("FooBar < 5", 11, "Expected a unit, found the end")
+        );
+        assert_eq!(
+            parsing_error("FooBar < m"),
+            invalid("FooBar < m", 10, "Expected a number, found 'm'")
+        );
+        assert_eq!(
+            parsing_error("Speed between 5 m/s or 10 m/s"),
+            invalid(
+                "Speed between 5 m/s or 10 m/s",
+                21,
+                "Expected 'and', found 'or'"
+            )
+        );
+        assert_eq!(
+            parsing_error("5 m/s < Speed > 10 m/s: ASIL B"),
+            invalid(
+                "5 m/s < Speed > 10 m/s",
+                15,
+                "Expected '<' or '<=', found '>'"
+            )
+        );
+        assert_eq!(
+            parsing_error("Speed < 5 m/s and Speed > 1 m/s"),
+            invalid(
+                "Speed < 5 m/s and Speed > 1 m/s",
+                15,
+                "Expected 'or' or the end of the dimension, found 'and'"
+            )
+        );
+        assert_eq!(
+            parsing_error("Weather in {Sunny,}"),
+            invalid("Weather in {Sunny,}", 19, "Expected a category, found '}'")
+        );
+        assert_eq!(
+            parsing_error("Delay between 5 ms and 3 ms"),
+            ParsingError::EmptyRange("Delay".to_string())
+        );
+    }
+
+    #[test]
+    fn test_quoted_and_categorical() {
+        assert_eq!(
+            text_to_dimension("\"Ratio a:b < c\" < 5 m").unwrap(),
+            Dimension::Numeric {
+                r#type: "Ratio a:b < c".into(),
+                covered: vec![NumericRange::RightOpen(f64::MIN..5.0)],
+                subset: SubsetRelationship::Guarantee,
+                uom: Some(UnitOfMeasure::new("m").unwrap()),
+            }
+        );
+        let weather = Dimension::Categorical {
+            r#type: "Weather".into(),
+            covered: vec!["Light rain".into(), "Sunny".into(), "a, b".into()]
+                .into_iter()
+                .collect(),
+            subset: SubsetRelationship::Guarantee,
+        };
+        assert_eq!(
+            text_to_dimension("weather in {Sunny, Light rain, \"a, b\"}: ASIL B").unwrap(),
+            weather
+        );
+        assert_eq!(
+            text_to_integrity_level("weather in {Sunny, Light rain, \"a, b\"}: ASIL B").unwrap(),
+            Some(IntegrityLevel::AsilB)
+        );
+        assert_eq!(
+            text_to_dimension("Level in {1,2,3}").unwrap(),
+            text_to_dimension("Level in {1, 2, 3}").unwrap()
+        );
+        assert_eq!(
+            dimension_to_text(&weather),
+            Some("Weather in {Light rain, Sunny, \"a, b\"}".to_string())
+        );
+    }
+
+    #[test]
+    fn test_several_dimensions() {
+        let dimensions = vec![
+            Dimension::Numeric {
+                r#type: "UnoccupiedTime".into(),
+                covered: vec![NumericRange::Inclusive(0.0..=2.0)],
+                subset: SubsetRelationship::Demand,
+                uom: Some(UnitOfMeasure::new("s").unwrap()),
+            },
+            Dimension::Categorical {
+                r#type: "PL".into(),
+                covered: vec!["PL d".into()].into_iter().collect(),
+                subset: SubsetRelationship::Demand,
+            },
+            Dimension::Binary {
+                r#type: "S1_Unoccupied".into(),
+            },
+        ];
+        let text = dimensions_to_text(&dimensions).unwrap();
+        assert_eq!(
+            text,
+            "UnoccupiedTime between 0 s and 2 s; \"PL\" in {PL d}; \"S1_Unoccupied\""
+        );
+        // the subset relationship has no textual form
+        assert_eq!(
+            text_to_dimensions(&text).unwrap(),
+            dimensions.iter().map(modulo_subset).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            text_to_integrity_level(&format!("{}: SIL 2", text)).unwrap(),
+            Some(IntegrityLevel::Sil2)
+        );
+        assert_eq!(dimensions_to_text(&[]), None);
+        assert!(text_to_dimensions("Approved;").is_err());
+    }
 }
@@ -2,8 +2,8 @@
 //
 // SPDX-License-Identifier: MIT
 
-use crate::dimensions::text_to_dimension;
-use crate::GetAttribute;
+use crate::dimensions::{text_to_dimension, text_to_integrity_level};
+use crate::{get_description, get_refinement, GetAttribute, Locate};
 use conserts_elements::elements::evidence::Evidence;
 use conserts_elements::elements::{demands::Demand, services::RequiredService};
 use conserts_error::ConSertError;
@@ -41,16 +41,35 @@ fn collect_rte_evidence(
         .map(|(index, rte)| {
             let name = rte.try_get_attribute("name")?.to_owned();
 
-            let description = rte.try_get_attribute("description")?.to_owned();
+            let refinement = refinement(rte)?;
 
-            let dimension = text_to_dimension(&description)?;
+            let dimension = text_to_dimension(&refinement).at(rte)?;
+            // evidence has no integrity level of its own, the one of the description is kept
+            let integrity = text_to_integrity_level(&refinement).ok().flatten();
 
             Ok(Arc::new(Evidence::new(
                 index,
                 name,
-                Some(description),
+                get_description(
+                    rte,
+                    &refinement,
+                    std::slice::from_ref(&dimension),
+                    integrity,
+                ),
                 dimension,
             )))
         })
         .collect()
 }
+
+/// Models of safeTbox describe runtime evidence by its description, exported models by a
+/// refinement like the one of guarantees.
+pub(super) fn refinement(rte: &Node) -> Result<String, ConSertError<Demand, RequiredService>> {
+    match rte
+        .descendants()
+        .any(|n| n.tag_name() == ExpandedName::from("refinement"))
+    {
+        true => get_refinement(rte),
+        false => Ok(rte.try_get_attribute("description")?.to_owned()),
+    }
+}
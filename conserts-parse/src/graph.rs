@@ -0,0 +1,83 @@
+// SPDX-FileCopyrightText: 2022 Fraunhofer Institute for Experimental Software Engineering IESE
+//
+// SPDX-License-Identifier: MIT
+
+use std::collections::BTreeMap;
+
+#[derive(Clone, Copy, PartialEq)]
+enum State {
+    Visiting,
+    Done,
+}
+
+/// Finds a cycle in the graph given by its edges. The cycle is returned as the list of nodes
+/// along it, starting and ending with the same node.
+pub(crate) fn find_cycle<S: AsRef<str>>(edges: &[(S, S)]) -> Option<Vec<String>> {
+    let mut successors: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for (from, to) in edges {
+        successors
+            .entry(from.as_ref())
+            .or_default()
+            .push(to.as_ref());
+    }
+
+    let mut states = BTreeMap::new();
+    let mut path = vec![];
+    for (from, _) in edges {
+        if let Some(cycle) = visit(from.as_ref(), &successors, &mut states, &mut path) {
+            return Some(cycle);
+        }
+    }
+    None
+}
+
+fn visit<'a>(
+    node: &'a str,
+    successors: &BTreeMap<&'a str, Vec<&'a str>>,
+    states: &mut BTreeMap<&'a str, State>,
+    path: &mut Vec<&'a str>,
+) -> Option<Vec<String>> {
+    match states.get(node) {
+        Some(State::Done) => return None,
+        Some(State::Visiting) => {
+            let start = path.iter().position(|n| n.eq(&node)).unwrap_or_default();
+            return Some(
+                path[start..]
+                    .iter()
+                    .chain(std::iter::once(&node))
+                    .map(|n| n.to_string())
+                    .collect(),
+            );
+        }
+        None => {}
+    }
+
+    let _ = states.insert(node, State::Visiting);
+    path.push(node);
+    for successor in successors.get(node).into_iter().flatten() {
+        if let Some(cycle) = visit(successor, successors, states, path) {
+            return Some(cycle);
+        }
+    }
+    let _ = path.pop();
+    let _ = states.insert(node, State::Done);
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_cycle() {
+        assert_eq!(find_cycle(&[("a", "b"), ("b", "c"), ("a", "c")]), None);
+        assert_eq!(
+            find_cycle(&[("e", "a"), ("a", "b"), ("b", "c"), ("c", "a")]),
+            Some(vec!["a".into(), "b".into(), "c".into(), "a".into()])
+        );
+        assert_eq!(
+            find_cycle(&[("a", "a")]),
+            Some(vec!["a".into(), "a".into()])
+        );
+    }
+}
@@ -2,16 +2,16 @@
 //
 // SPDX-License-Identifier: MIT
 
-use super::get_refinement;
-use crate::dimensions::text_to_dimension;
-use crate::GetAttribute;
+use super::{get_description, get_refinement};
+use crate::dimensions::{text_to_dimensions, text_to_integrity_level};
+use crate::{location, GetAttribute, Locate};
 use conserts_elements::elements::consert_tree::*;
 use conserts_elements::elements::demands::Demand;
 use conserts_elements::elements::evidence::Evidence;
 use conserts_elements::elements::guarantees::{Guarantee, GuaranteePropagation};
 use conserts_elements::elements::services::RequiredService;
 use conserts_error::{ConSertError, ParsingError};
-use roxmltree::{Document, ExpandedName};
+use roxmltree::{Document, ExpandedName, Node};
 use std::sync::{Arc, Mutex};
 
 pub(super) fn parse_guarantees(
@@ -21,6 +21,7 @@ pub(super) fn parse_guarantees(
     gates: &[Gate],
     demands: &[Arc<Mutex<Demand>>],
 ) -> Result<Vec<Arc<Guarantee>>, ConSertError<Demand, RequiredService>> {
+    let mut subtrees = Subtrees::default();
     doc.descendants()
         .filter(|n| n.tag_name() == ExpandedName::from("guarantees"))
         .enumerate()
@@ -30,20 +31,25 @@ pub(super) fn parse_guarantees(
             let cst = extract_cst(
                 guarantee_propagations,
                 &path,
+                &mut subtrees,
                 runtime_evidence,
                 gates,
                 demands,
-            )?;
+            )
+            .at(&g)?;
 
             let refinement = get_refinement(&g)?;
+            let dimensions = text_to_dimensions(&refinement).at(&g)?;
+            let integrity = text_to_integrity_level(&refinement).at(&g)?;
 
-            Ok(Arc::new(Guarantee::new(
+            Ok(Arc::new(Guarantee {
+                id: ident.to_string(),
+                description: get_description(&g, &refinement, &dimensions, integrity),
+                dimensions,
+                integrity,
                 index,
-                ident,
-                Some(refinement.clone()),
-                text_to_dimension(&refinement)?,
                 cst,
-            )))
+            }))
         })
         .collect()
 }
@@ -54,16 +60,44 @@ pub(super) fn parse_guarantee_propagations(
     doc.descendants()
         .filter(|n| n.tag_name() == ExpandedName::from("guaranteePropagations"))
         .map(|n| {
-            Ok(GuaranteePropagation::new(
-                "",
-                "",
-                n.try_get_attribute("sourceElement")?,
-                n.try_get_attribute("targetElement")?,
-            ))
+            let source = n.try_get_attribute("sourceElement")?;
+            let target = n.try_get_attribute("targetElement")?;
+            for path in [source, target] {
+                if !refers_to_element(doc, path) {
+                    return Err(ParsingError::UnknownElement {
+                        id: element_name(path).to_string(),
+                        from: element_name(source).to_string(),
+                        to: element_name(target).to_string(),
+                    }
+                    .at(location(&n))
+                    .into());
+                }
+            }
+            Ok(GuaranteePropagation::new("", "", source, target))
         })
         .collect()
 }
 
+/// The last segment of a path, e.g. `@gates.0`.
+pub(super) fn element_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+pub(super) fn refers_to_element(doc: &Document, path: &str) -> bool {
+    let (tag, index) = match element_name(path)
+        .trim_start_matches('@')
+        .split_once('.')
+        .and_then(|(tag, index)| Some((tag, index.parse::<usize>().ok()?)))
+    {
+        Some(element) => element,
+        None => return false,
+    };
+    doc.descendants()
+        .filter(|n| n.tag_name() == ExpandedName::from(tag))
+        .nth(index)
+        .is_some()
+}
+
 // Consider a test
 pub(super) fn parse_gates(
     doc: &Document,
@@ -72,19 +106,31 @@ pub(super) fn parse_gates(
         .filter(|n| n.tag_name() == ExpandedName::from("gates"))
         .enumerate()
         .map(|(index, node)| {
-            let function = match &(node.try_get_attribute("gateType")?.to_lowercase())[..] {
-                "and" => GateFunction::And,
-                "or" => GateFunction::Or,
-                function => return Err(ParsingError::UnsupportedGate(function.to_string()).into()),
-            };
-            Ok(Gate::new(format!("Gate{}", index), index, function))
+            Ok(Gate::new(
+                format!("Gate{}", index),
+                index,
+                gate_function(&node)?,
+            ))
         })
         .collect()
 }
 
+pub(super) fn gate_function(
+    node: &Node,
+) -> Result<GateFunction, ConSertError<Demand, RequiredService>> {
+    match &(node.try_get_attribute("gateType")?.to_lowercase())[..] {
+        "and" => Ok(GateFunction::And),
+        "or" => Ok(GateFunction::Or),
+        function => Err(ParsingError::UnsupportedGate(function.to_string())
+            .at(location(node))
+            .into()),
+    }
+}
+
 fn extract_cst(
     guarantee_propagations: &[GuaranteePropagation],
     path: &str,
+    subtrees: &mut Subtrees,
     runtime_evidence: &[Arc<Evidence>],
     gates: &[Gate],
     demands: &[Arc<Mutex<Demand>>],
@@ -96,16 +142,12 @@ fn extract_cst(
     match root {
         Some(root) => grow_cst(
             &root.source_path(),
+            subtrees,
             guarantee_propagations,
             runtime_evidence,
             gates,
             demands,
         ),
-        None => Ok(Tree {
-            data: Box::new(TreeNode {
-                element: ConsertTreeElement::Tautology,
-                children: vec![],
-            }),
-        }),
+        None => Ok(Tree::leaf(ConsertTreeElement::Tautology)),
     }
 }
@@ -8,19 +8,25 @@
 use askama::Template;
 use conserts_elements::guarantees::Guarantee;
 use conserts_elements::{consert::Consert, demands::Demand, elements, services::RequiredService};
-use conserts_error::{ConSertError, ParsingError};
+use conserts_error::{ConSertError, Location, ParsingError};
 use roxmltree::{self, ExpandedName, Node};
 use sha3::{Digest, Sha3_256};
-use std::convert::{TryFrom, TryInto};
+use std::convert::TryFrom;
 use std::sync::{Arc, Mutex};
 use std::{path::Path, rc::Rc};
 
+pub mod convert;
 mod demands;
-mod dimensions;
+pub mod dimensions;
 mod evidence;
+mod graph;
 mod guarantees;
+pub mod properties;
+pub mod quantities;
 mod services;
 mod templates;
+mod tokens;
+pub mod validate;
 mod yml;
 
 #[derive(Debug)]
@@ -68,12 +74,14 @@ impl TryFrom<yml::YamlConsertElements> for ConsertElements {
 
     fn try_from(value: yml::YamlConsertElements) -> Result<Self, Self::Error> {
         value.unique_ids()?;
+        value.known_elements()?;
+        value.valid_dimensions()?;
 
         let evidence = value.evidence();
         let demands = value.demands();
         let guarantees = value.guarantees()?;
 
-        let provided_services = value.provided_services();
+        let provided_services = value.provided_services(&guarantees);
         let required_services = value.required_services();
 
         Ok(Self {
@@ -114,8 +122,32 @@ pub trait GetAttribute {
 
 impl GetAttribute for roxmltree::Node<'_, '_> {
     fn try_get_attribute(&self, attribute: &str) -> Result<&str, ParsingError> {
-        self.attribute(attribute)
-            .ok_or_else(|| ParsingError::MissingXmlAttribute(attribute.to_string()))
+        self.attribute(attribute).ok_or_else(|| {
+            ParsingError::MissingXmlAttribute(attribute.to_string()).at(location(self))
+        })
+    }
+}
+
+/// The position of a node in the XML model.
+fn location(node: &Node) -> Location {
+    let pos = node.document().text_pos_at(node.range().start);
+    Location {
+        line: pos.row as usize,
+        column: pos.col as usize,
+    }
+}
+
+trait Locate {
+    /// Attaches the position of the node to parsing errors without a location.
+    fn at(self, node: &Node) -> Self;
+}
+
+impl<T> Locate for Result<T, ConSertError<Demand, RequiredService>> {
+    fn at(self, node: &Node) -> Self {
+        self.map_err(|error| match error {
+            ConSertError::Parsing { source } => source.at(location(node)).into(),
+            error => error,
+        })
     }
 }
 
@@ -124,22 +156,44 @@ pub fn get_refinement(
 ) -> Result<String, ConSertError<elements::demands::Demand, elements::services::RequiredService>> {
     Ok(g.descendants()
         .find(|n| n.tag_name() == ExpandedName::from("refinement"))
-        .ok_or_else(|| ParsingError::MissingXmlDescendent("refinement".to_string()))?
+        .ok_or_else(|| {
+            ParsingError::MissingXmlDescendent("refinement".to_string()).at(location(g))
+        })?
         .try_get_attribute("name")?
         .to_string())
 }
 
+/// The description of an element. Models of safeTbox describe elements by their refinement,
+/// exported models in the description attribute, with a refinement derived from the
+/// dimensions if the element has no description.
+fn get_description(
+    node: &Node,
+    refinement: &str,
+    dimensions: &[conserts_elements::dimension::Dimension],
+    integrity: Option<elements::integrity::IntegrityLevel>,
+) -> Option<String> {
+    match node.attribute("description") {
+        Some(description) if !description.is_empty() => Some(description.to_string()),
+        _ if refinement.eq(&templates::refinement(&None, dimensions, integrity)) => None,
+        _ => Some(refinement.to_string()),
+    }
+}
+
 pub fn get_functional_service_type(
     n: Node,
 ) -> Result<String, ConSertError<elements::demands::Demand, elements::services::RequiredService>> {
     Ok(n.descendants()
         .find(|n| n.tag_name() == ExpandedName::from("functionalServiceType"))
-        .ok_or_else(|| ParsingError::MissingXmlDescendent("functionalServiceType".to_string()))?
+        .ok_or_else(|| {
+            ParsingError::MissingXmlDescendent("functionalServiceType".to_string()).at(location(&n))
+        })?
         .try_get_attribute("name")?
         .to_string())
 }
 
-fn parse_model(xml: &str) -> Result<roxmltree::Document, ConSertError<Demand, RequiredService>> {
+fn parse_model(
+    xml: &str,
+) -> Result<roxmltree::Document<'_>, ConSertError<Demand, RequiredService>> {
     Ok(roxmltree::Document::parse(xml).map_err(ParsingError::Xml)?)
 }
 
@@ -156,6 +210,12 @@ fn hash_model(model: &str) -> String {
     hex::encode(hasher.finalize())
 }
 
+/// The JSON Schema of the YAML and JSON model formats.
+pub fn json_schema() -> Result<String, ConSertError<Demand, RequiredService>> {
+    let schema = schemars::schema_for!(yml::YamlConsertElements);
+    Ok(serde_json::to_string_pretty(&schema)?)
+}
+
 pub trait Yaml {
     fn to_yaml(&self) -> Result<String, ConSertError<Demand, RequiredService>>;
     fn from_yaml<P: AsRef<Path>>(
@@ -170,7 +230,8 @@ pub trait Yaml {
 
 impl Yaml for Consert {
     fn to_yaml(&self) -> Result<String, ConSertError<Demand, RequiredService>> {
-        unimplemented!()
+        let elements = yml::YamlConsertElements::try_from(self)?;
+        Ok(serde_yaml::to_string(&elements)?)
     }
 
     fn from_yaml<P: AsRef<Path>>(
@@ -182,8 +243,9 @@ impl Yaml for Consert {
         let path = path.as_ref().to_string_lossy().to_string();
 
         use std::str::FromStr;
-        let elements = yml::YamlConsertElements::from_str(doc.as_str())?;
-        let elements: ConsertElements = elements.try_into()?;
+        let elements: ConsertElements = yml::YamlConsertElements::from_str(doc.as_str())
+            .and_then(ConsertElements::try_from)
+            .map_err(|error| yml::locate(error, &doc))?;
 
         Ok(Consert::new(
             name,
@@ -208,6 +270,59 @@ impl Yaml for Consert {
     }
 }
 
+pub trait Json {
+    fn to_json(&self) -> Result<String, ConSertError<Demand, RequiredService>>;
+    fn from_json<P: AsRef<Path>>(
+        path: &P,
+        checksum: String,
+        doc: String,
+    ) -> Result<Consert, ConSertError<Demand, RequiredService>>;
+    fn from_path_json<P: AsRef<Path>>(
+        path: &P,
+    ) -> Result<Consert, ConSertError<Demand, RequiredService>>;
+}
+
+impl Json for Consert {
+    fn to_json(&self) -> Result<String, ConSertError<Demand, RequiredService>> {
+        let elements = yml::YamlConsertElements::try_from(self)?;
+        Ok(serde_json::to_string_pretty(&elements)?)
+    }
+
+    fn from_json<P: AsRef<Path>>(
+        path: &P,
+        checksum: String,
+        doc: String,
+    ) -> Result<Consert, ConSertError<Demand, RequiredService>> {
+        let name = Consert::path_to_name(path)?;
+        let path = path.as_ref().to_string_lossy().to_string();
+
+        let elements: ConsertElements = yml::YamlConsertElements::from_json(doc.as_str())
+            .and_then(ConsertElements::try_from)
+            .map_err(|error| yml::locate(error, &doc))?;
+
+        Ok(Consert::new(
+            name,
+            path,
+            checksum,
+            elements.guarantees(),
+            elements.demands(),
+            elements.provided_services(),
+            elements.required_services(),
+            elements.evidence(),
+        ))
+    }
+
+    fn from_path_json<P: AsRef<Path>>(
+        path: &P,
+    ) -> Result<Consert, ConSertError<Demand, RequiredService>> {
+        let model = read_model_file(path.as_ref())?;
+
+        let checksum = hash_model(&model);
+
+        Consert::from_json(path, checksum, model)
+    }
+}
+
 pub trait Xml {
     fn to_xml(&self) -> Result<String, ConSertError<Demand, RequiredService>>;
     fn from_xml<P: AsRef<Path>>(
@@ -261,9 +376,505 @@ impl Xml for Consert {
 
 #[cfg(test)]
 mod tests {
-    use super::Yaml;
+    use super::{Json, Xml, Yaml};
     use conserts_elements::consert::Consert;
+    use conserts_elements::consert_tree::{ConsertTree, ConsertTreeElement};
+    use conserts_elements::uom::{Quantities, UnitOfMeasure};
+    use conserts_elements::{demands::Demand, services::RequiredService};
+    use conserts_error::{ConSertError, Location, ParsingError, UnitOfMeasureError};
     use pretty_assertions::assert_eq;
+    use std::path::Path;
+
+    fn load(path: &Path) -> Consert {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("yml") => Consert::from_path_yaml(&path).unwrap(),
+            _ => Consert::from_path_xml(&path).unwrap(),
+        }
+    }
+
+    fn describe_tree(cst: &ConsertTree) -> String {
+        let node = &cst.data;
+        let element = match &node.element {
+            ConsertTreeElement::RuntimeEvidence(_, evidence) => evidence.id.clone(),
+            ConsertTreeElement::Demand(_, demand) => demand.lock().unwrap().id.clone(),
+            ConsertTreeElement::Gate(id, _, function) => format!("{}:{:?}", id, function),
+            ConsertTreeElement::Tautology => "true".to_string(),
+            ConsertTreeElement::Contradiction => "false".to_string(),
+        };
+        let children = node
+            .children
+            .iter()
+            .map(describe_tree)
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("{}({})", element, children)
+    }
+
+    fn assert_equivalent(expected: &Consert, actual: &Consert) {
+        let evidence = |c: &Consert| {
+            c.evidence()
+                .iter()
+                .map(|e| (e.id.clone(), e.description.clone(), e.dimension.clone()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(evidence(expected), evidence(actual));
+
+        let guarantees = |c: &Consert| {
+            c.guarantees()
+                .iter()
+                .map(|g| {
+                    (
+                        g.id.clone(),
+                        g.description.clone(),
+                        g.dimensions.clone(),
+                        describe_tree(&g.cst),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(guarantees(expected), guarantees(actual));
+
+        let demands = |c: &Consert| {
+            c.demands()
+                .iter()
+                .map(|d| {
+                    let d = d.lock().unwrap();
+                    (d.id.clone(), d.description.clone(), d.dimensions.clone())
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(demands(expected), demands(actual));
+
+        let provided_services = |c: &Consert| {
+            c.provided_services()
+                .iter()
+                .map(|p| {
+                    (
+                        p.ident.clone(),
+                        p.functional_service_type.clone(),
+                        p.guarantees
+                            .iter()
+                            .map(|g| g.id.clone())
+                            .collect::<Vec<_>>(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(provided_services(expected), provided_services(actual));
+
+        let required_services = |c: &Consert| {
+            c.required_services()
+                .iter()
+                .map(|r| {
+                    (
+                        r.ident.clone(),
+                        r.functional_service_type.clone(),
+                        r.demands
+                            .iter()
+                            .map(|d| d.lock().unwrap().id.clone())
+                            .collect::<Vec<_>>(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(required_services(expected), required_services(actual));
+    }
+
+    #[test]
+    fn test_yml_round_trip() {
+        let round_trip = [
+            "DEIS_DemoFollowerTruckSystem.model",
+            "DEIS_DemoLeaderTruckSystem.model",
+            "FabOS_Scanner.yml",
+            "FabOS_Scanner2.yml",
+        ];
+        // evidence names are not unique, which YAML ids have to be
+        let renamed = ["DEIS_DemoLeaderTruckSystemIncompatible.model"];
+        // rejected while parsing, see test_valid_yml
+        let invalid = ["InvalidCovered.yml", "InvalidGuarantee.yml", "Truck.model"];
+
+        for entry in std::fs::read_dir("../models").unwrap() {
+            let file_name = entry.unwrap().file_name().into_string().unwrap();
+            assert!(
+                round_trip
+                    .iter()
+                    .chain(renamed.iter())
+                    .chain(invalid.iter())
+                    .any(|m| m.eq(&file_name)),
+                "{} is not covered by the round-trip test",
+                file_name
+            );
+        }
+
+        for model in round_trip {
+            let path = Path::new("../models").join(model);
+            let consert = load(&path);
+            let yaml = consert.to_yaml().unwrap();
+            let parsed = Consert::from_yaml(&path, consert.checksum(), yaml.clone()).unwrap();
+            assert_equivalent(&consert, &parsed);
+            assert_eq!(parsed.to_yaml().unwrap(), yaml);
+        }
+
+        for model in renamed {
+            let path = Path::new("../models").join(model);
+            let consert = load(&path);
+            let yaml = consert.to_yaml().unwrap();
+            let parsed = Consert::from_yaml(&path, consert.checksum(), yaml.clone()).unwrap();
+            let ids = parsed
+                .evidence()
+                .iter()
+                .map(|e| e.id.clone())
+                .collect::<Vec<_>>();
+            assert_eq!(ids, vec!["RtE3", "RtE3_2"]);
+            assert_eq!(parsed.to_yaml().unwrap(), yaml);
+        }
+    }
+
+    #[test]
+    fn test_xml_round_trip() {
+        for model in [
+            "DEIS_DemoFollowerTruckSystem.model",
+            "DEIS_DemoLeaderTruckSystem.model",
+            "DEIS_DemoLeaderTruckSystemIncompatible.model",
+        ] {
+            let path = Path::new("../models").join(model);
+            let consert = load(&path);
+            let xml = consert.to_xml().unwrap();
+            let doc = super::parse_model(&xml).unwrap();
+            let parsed = Consert::from_xml(&path, consert.checksum(), doc).unwrap();
+            assert_equivalent(&consert, &parsed);
+            assert_eq!(parsed.to_xml().unwrap(), xml);
+        }
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        for model in [
+            "DEIS_DemoFollowerTruckSystem.model",
+            "DEIS_DemoLeaderTruckSystem.model",
+            "FabOS_Scanner.yml",
+            "FabOS_Scanner2.yml",
+        ] {
+            let path = Path::new("../models").join(model);
+            let consert = load(&path);
+            let json = consert.to_json().unwrap();
+            let parsed = Consert::from_json(&path, consert.checksum(), json.clone()).unwrap();
+            assert_equivalent(&consert, &parsed);
+            assert_eq!(parsed.to_json().unwrap(), json);
+            assert_eq!(parsed.to_yaml().unwrap(), consert.to_yaml().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_json_schema() {
+        // the published schema is generated by `conserts schema`
+        assert_eq!(
+            include_str!("../schema/consert.schema.json"),
+            format!("{}\n", super::json_schema().unwrap())
+        );
+
+        let schema: serde_json::Value =
+            serde_json::from_str(&super::json_schema().unwrap()).unwrap();
+        let [si, custom] = schema["definitions"]["UnitOfMeasure"]["anyOf"]
+            .as_array()
+            .unwrap()
+            .as_slice()
+        else {
+            panic!("expected the units of uom::si and of custom quantities");
+        };
+        let si = si["enum"].as_array().unwrap();
+        for unit in si {
+            assert!(UnitOfMeasure::new(unit.as_str().unwrap()).is_ok());
+        }
+        assert!(!si.contains(&"parsec".into()));
+        assert_eq!(custom["pattern"], "^[a-z][a-z0-9_]*$");
+    }
+
+    fn parsing_error(
+        result: Result<Consert, ConSertError<Demand, RequiredService>>,
+    ) -> ParsingError {
+        match result {
+            Err(ConSertError::Parsing { source }) => source,
+            r => panic!("expected a parsing error, got {:?}", r),
+        }
+    }
+
+    #[test]
+    fn test_yml_error_location() {
+        let path = Path::new("../models/FabOS_Scanner.yml");
+        let model = std::fs::read_to_string(path).unwrap();
+
+        let duplicate = model.replacen("id: E_Approved", "id: E_DistanceBound", 1);
+        let error = parsing_error(Consert::from_yaml(&path, "".into(), duplicate));
+        assert_eq!(
+            error.kind(),
+            &ParsingError::NonUniqueIds(vec!["E_DistanceBound".into()])
+        );
+        assert_eq!(
+            error.location(),
+            Some(Location {
+                line: 35,
+                column: 5
+            })
+        );
+
+        let unknown = model.replace(
+            "  - from: E_Approved\n    to: G_Approved",
+            "  - from: E_Missing\n    to: G_Approved",
+        );
+        let error = parsing_error(Consert::from_yaml(&path, "".into(), unknown));
+        assert_eq!(
+            error.kind(),
+            &ParsingError::UnknownElement {
+                id: "E_Missing".into(),
+                from: "E_Missing".into(),
+                to: "G_Approved".into()
+            }
+        );
+        assert_eq!(
+            error.location(),
+            Some(Location {
+                line: 70,
+                column: 5
+            })
+        );
+
+        let error = parsing_error(Consert::from_path_yaml(&"../models/InvalidGuarantee.yml"));
+        assert!(matches!(error.kind(), ParsingError::Syntax(_)));
+        assert_eq!(error.location(), Some(Location { line: 7, column: 7 }));
+    }
+
+    #[test]
+    fn test_yml_ordinal_level() {
+        let path = Path::new("../models/FabOS_Scanner.yml");
+        let model = std::fs::read_to_string(path).unwrap();
+        let ordinal = |level: &str| {
+            model.replace(
+                "      Binary:\n        type: HSEApprovedSetup",
+                &format!(
+                    "      Ordinal:\n        type: HSEApproval\n        levels: [None, Basic, Full]\n        level: {}\n        bound: AtLeast\n        subset: Guarantee",
+                    level
+                ),
+            )
+        };
+
+        assert!(Consert::from_yaml(&path, "".into(), ordinal("Basic")).is_ok());
+
+        let error = parsing_error(Consert::from_yaml(&path, "".into(), ordinal("Partial")));
+        assert_eq!(
+            error.kind(),
+            &ParsingError::UnknownLevel {
+                r#type: "HSEApproval".into(),
+                level: "Partial".into()
+            }
+        );
+        assert_eq!(
+            error.location(),
+            Some(Location {
+                line: 41,
+                column: 9
+            })
+        );
+    }
+
+    #[test]
+    fn test_xml_error_location() {
+        let error = parsing_error(Consert::from_path_xml(&"../models/Truck.model"));
+        assert_eq!(
+            error.location(),
+            Some(Location {
+                line: 47,
+                column: 7
+            })
+        );
+
+        let path = Path::new("../models/DEIS_DemoFollowerTruckSystem.model");
+        let model = std::fs::read_to_string(path).unwrap().replacen(
+            "@runtimeEvidence.2",
+            "@runtimeEvidence.7",
+            1,
+        );
+        let doc = super::parse_model(&model).unwrap();
+        let error = parsing_error(Consert::from_xml(&path, "".into(), doc));
+        assert_eq!(
+            error.kind(),
+            &ParsingError::UnknownElement {
+                id: "@runtimeEvidence.7".into(),
+                from: "@runtimeEvidence.7".into(),
+                to: "@gates.0".into()
+            }
+        );
+        assert_eq!(
+            error.location(),
+            Some(Location {
+                line: 56,
+                column: 7
+            })
+        );
+    }
+
+    #[test]
+    fn test_cycle() {
+        let path = Path::new("../models/FabOS_Scanner.yml");
+        let model = std::fs::read_to_string(path)
+            .unwrap()
+            .replace(
+                "gates: []",
+                "gates:\n  - id: A\n    function: And\n  - id: B\n    function: Or",
+            )
+            .replace(
+                "tree_propagations:\n",
+                "tree_propagations:\n  - from: A\n    to: G_Approved\n  - from: B\n    to: A\n  - from: A\n    to: B\n",
+            );
+        let error = parsing_error(Consert::from_yaml(&path, "".into(), model));
+        assert_eq!(
+            error.kind(),
+            &ParsingError::Cycle(vec!["A".into(), "B".into(), "A".into()])
+        );
+        assert_eq!(
+            error.location(),
+            Some(Location {
+                line: 72,
+                column: 5
+            })
+        );
+
+        let path = Path::new("../models/DEIS_DemoFollowerTruckSystem.model");
+        let model = std::fs::read_to_string(path)
+            .unwrap()
+            .replace("@demands.0\" targetElement", "@gates.1\" targetElement")
+            .replace("@demands.1\" targetElement", "@gates.0\" targetElement");
+        let doc = super::parse_model(&model).unwrap();
+        let error = parsing_error(Consert::from_xml(&path, "".into(), doc));
+        assert_eq!(
+            error.kind(),
+            &ParsingError::Cycle(vec![
+                "@gates.0".into(),
+                "@gates.1".into(),
+                "@gates.0".into()
+            ])
+        );
+    }
+
+    #[test]
+    fn test_shared_subtrees() {
+        let model = r#"
+guarantees:
+  - id: G1
+    dimensions:
+      - Binary:
+          type: First
+  - id: G2
+    dimensions:
+      - Binary:
+          type: Second
+evidence:
+  - id: E1
+    dimension:
+      Binary:
+        type: One
+  - id: E2
+    dimension:
+      Binary:
+        type: Two
+demands: []
+gates:
+  - id: Shared
+    function: Or
+tree_propagations:
+  - from: E1
+    to: Shared
+  - from: E2
+    to: Shared
+  - from: Shared
+    to: G1
+  - from: Shared
+    to: G2
+  - from: E1
+    to: G2
+required_services: []
+provided_services: []
+"#;
+        let consert = Consert::from_yaml(&"shared.yml", "".into(), model.into()).unwrap();
+        let guarantees = consert.guarantees();
+        let shared = &guarantees[0].cst;
+        assert_eq!(describe_tree(shared), "Shared:Or(E1(), E2())");
+        assert!(guarantees[1].cst.data.children[0].shares(shared));
+        assert!(shared.data.children[0].shares(&guarantees[1].cst.data.children[1]));
+    }
+
+    #[test]
+    fn test_yml_custom_quantities() {
+        let model = r#"
+guarantees:
+  - id: G1
+    dimensions:
+      - Binary:
+          type: Lit
+evidence:
+  - id: E1
+    dimension:
+      Numeric:
+        type: Illuminance
+        covered:
+          - Inclusive:
+              start: 0.5
+              end: 2.0
+        subset: Guarantee
+        uom: klx
+demands: []
+gates: []
+tree_propagations:
+  - from: E1
+    to: G1
+required_services: []
+provided_services: []
+quantities:
+  - name: Illuminance
+    units:
+      - name: lux
+        abbreviation: lx
+        factor: 1.0
+      - name: kilolux
+        abbreviation: klx
+        factor: 1000.0
+"#;
+        let consert = Consert::from_yaml(&"lit.yml", "".into(), model.into()).unwrap();
+        let dimension = consert.evidence()[0].dimension.clone();
+        let mut quantities = Quantities::default();
+        for quantity in crate::quantities::used_by(&consert) {
+            quantities.declare(quantity).unwrap();
+        }
+        let in_lux = quantities.scope(|| {
+            crate::dimensions::text_to_dimension("Illuminance between 400 lx and 2500 lx").unwrap()
+        });
+        assert_eq!(
+            dimension.subset_of(&in_lux),
+            conserts_elements::dimension::SubsetResult::True
+        );
+
+        let yaml = consert.to_yaml().unwrap();
+        assert!(yaml.contains("quantities:"));
+        let parsed = Consert::from_yaml(&"lit.yml", "".into(), yaml).unwrap();
+        assert_eq!(parsed.evidence()[0].dimension, dimension);
+
+        // the declarations of a model do not outlive reading it
+        assert!(UnitOfMeasure::new("klx").is_err());
+        let redeclared = model.replace("factor: 1000.0", "factor: 100.0");
+        let parsed = Consert::from_yaml(&"lit.yml", "".into(), redeclared.clone()).unwrap();
+        assert_ne!(parsed.evidence()[0].dimension, dimension);
+
+        // but a quantity is declared once per project
+        let error = quantities
+            .scope(|| Consert::from_yaml(&"lit.yml", "".into(), redeclared))
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            ConSertError::UnitOfMeasure {
+                source: UnitOfMeasureError::InvalidDeclaration { .. }
+            }
+        ));
+    }
 
     #[test]
     fn test_yml_input() {
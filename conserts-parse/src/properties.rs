@@ -0,0 +1,160 @@
+// SPDX-FileCopyrightText: 2022 Fraunhofer Institute for Experimental Software Engineering IESE
+//
+// SPDX-License-Identifier: MIT
+
+use crate::convert::Format;
+use conserts_elements::evaluation::{Properties, Value};
+use conserts_elements::uom::UnitOfMeasure;
+use conserts_elements::{demands::Demand, services::RequiredService};
+use conserts_error::{ConSertError, ParsingError};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::path::Path;
+
+/// A value as written in a snapshot file, e.g. `true`, `0.9`, `42 m` or `ASIL-B`.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Entry {
+    Boolean(bool),
+    Number(f64),
+    Text(String),
+}
+
+impl TryFrom<Entry> for Value {
+    type Error = ConSertError<Demand, RequiredService>;
+
+    fn try_from(entry: Entry) -> Result<Self, Self::Error> {
+        Ok(match entry {
+            Entry::Boolean(value) => Value::Boolean(value),
+            Entry::Number(value) => Value::Number(value),
+            Entry::Text(text) => {
+                let text = text.trim();
+                match quantity(text) {
+                    Some((value, unit)) => Value::Quantity(value, unit),
+                    None => match text.parse() {
+                        Ok(value) => Value::Number(value),
+                        Err(_) => Value::Category(text.to_string()),
+                    },
+                }
+            }
+        })
+    }
+}
+
+/// A number followed by a known unit, with or without a space in between, e.g. `42 m` or
+/// `42m`. Text like `2 lanes` is no quantity.
+fn quantity(text: &str) -> Option<(f64, UnitOfMeasure)> {
+    text.char_indices().rev().find_map(|(index, _)| {
+        let (value, unit) = text.split_at(index);
+        let value = value.trim_end();
+        let unit = unit.trim();
+        if unit.is_empty() || !value.ends_with(|c: char| c.is_ascii_digit() || c == '.') {
+            return None;
+        }
+        Some((value.parse().ok()?, UnitOfMeasure::new(unit).ok()?))
+    })
+}
+
+/// Reads a snapshot of runtime properties, mapping ids of evidence and demands to values.
+pub fn from_str(
+    format: Format,
+    text: &str,
+) -> Result<Properties, ConSertError<Demand, RequiredService>> {
+    let entries: BTreeMap<String, Entry> = match format {
+        Format::Yaml => serde_yaml::from_str(text)?,
+        Format::Json => serde_json::from_str(text)?,
+        Format::Xml => {
+            return Err(ParsingError::Other(
+                "Only .yml and .json supported for properties".to_string(),
+            )
+            .into())
+        }
+    };
+    entries
+        .into_iter()
+        .map(|(id, entry)| Ok((id, Value::try_from(entry)?)))
+        .collect()
+}
+
+#[cfg(not(tarpaulin_include))] // IO function
+pub fn load<P: AsRef<Path>>(path: &P) -> Result<Properties, ConSertError<Demand, RequiredService>> {
+    let text = crate::read_model_file(path.as_ref())?;
+    let format = Format::from_path(path).ok_or_else(|| {
+        ParsingError::Other("Only .yml and .json supported for properties".to_string())
+    })?;
+    from_str(format, &text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_from_str() {
+        let properties = from_str(
+            Format::Yaml,
+            "
+E_DistanceBound: 42 m
+E_Approved: true
+E_Integrity: ASIL-B
+E_Confidence: 0.9
+E_Count: '-3'
+",
+        )
+        .unwrap();
+        assert_eq!(
+            properties,
+            vec![
+                ("E_Approved", Value::Boolean(true)),
+                ("E_Confidence", Value::Number(0.9)),
+                ("E_Count", Value::Number(-3.0)),
+                (
+                    "E_DistanceBound",
+                    Value::Quantity(42.0, UnitOfMeasure::new("m").unwrap())
+                ),
+                ("E_Integrity", Value::Category("ASIL-B".into())),
+            ]
+            .into_iter()
+            .map(|(id, value)| (id.to_string(), value))
+            .collect::<Properties>()
+        );
+
+        let properties = from_str(Format::Json, r#"{"Latency": "3.5 ms"}"#).unwrap();
+        assert_eq!(
+            properties["Latency"],
+            Value::Quantity(3.5, UnitOfMeasure::new("ms").unwrap())
+        );
+
+        // the unit may follow the number directly, text without a known unit is a category
+        let properties = from_str(
+            Format::Yaml,
+            "
+E_Distance: 42m
+E_Speed: 1.5e1km/h
+E_Lanes: 2 lanes
+E_Range: 42 parsecs
+E_Sector: 7G
+",
+        )
+        .unwrap();
+        assert_eq!(
+            properties["E_Distance"],
+            Value::Quantity(42.0, UnitOfMeasure::new("m").unwrap())
+        );
+        assert_eq!(
+            properties["E_Speed"],
+            Value::Quantity(15.0, UnitOfMeasure::new("km/h").unwrap())
+        );
+        assert_eq!(properties["E_Lanes"], Value::Category("2 lanes".into()));
+        assert_eq!(properties["E_Range"], Value::Category("42 parsecs".into()));
+        assert_eq!(properties["E_Sector"], Value::Category("7G".into()));
+    }
+
+    #[test]
+    fn test_from_str_invalid() {
+        assert!(from_str(Format::Yaml, "- 42").is_err());
+        assert!(from_str(Format::Xml, "").is_err());
+    }
+}
@@ -0,0 +1,124 @@
+// SPDX-FileCopyrightText: 2022 Fraunhofer Institute for Experimental Software Engineering IESE
+//
+// SPDX-License-Identifier: MIT
+
+use crate::convert::Format;
+use conserts_elements::uom::{CustomQuantity, Quantities};
+use conserts_elements::{consert::Consert, demands::Demand, services::RequiredService};
+use conserts_error::{ConSertError, ParsingError};
+use serde::Deserialize;
+use std::path::Path;
+
+/// The quantities declared by a model or a project file. They have to be in scope while the
+/// dimensions using their units are read.
+#[derive(Debug, Default, Deserialize)]
+pub struct Declarations {
+    #[serde(default)]
+    pub quantities: Vec<CustomQuantity>,
+}
+
+impl Declarations {
+    pub fn declare(
+        self,
+        quantities: &mut Quantities,
+    ) -> Result<(), ConSertError<Demand, RequiredService>> {
+        for quantity in self.quantities {
+            quantities.declare(quantity)?;
+        }
+        Ok(())
+    }
+}
+
+/// Adds the quantities of a YAML or JSON project file, shared by several models.
+pub fn load<P: AsRef<Path>>(
+    path: &P,
+    quantities: &mut Quantities,
+) -> Result<(), ConSertError<Demand, RequiredService>> {
+    let text = crate::read_model_file(path.as_ref())?;
+    let declarations: Declarations = match Format::from_path(path) {
+        Some(Format::Yaml) => serde_yaml::from_str(&text)?,
+        Some(Format::Json) => serde_json::from_str(&text)?,
+        _ => {
+            return Err(ParsingError::Other(
+                "Only .yml and .json supported for quantities".to_string(),
+            )
+            .into())
+        }
+    };
+    declarations.declare(quantities)
+}
+
+/// The custom quantities the dimensions of the ConSert refer to, each once.
+pub(crate) fn used_by(consert: &Consert) -> Vec<CustomQuantity> {
+    #![allow(clippy::unwrap_used)]
+    let demands = consert
+        .demands()
+        .iter()
+        .flat_map(|d| d.lock().unwrap().dimensions.clone())
+        .collect::<Vec<_>>();
+    let guarantees = consert.guarantees();
+    let evidence = consert.evidence();
+    let mut quantities: Vec<CustomQuantity> = vec![];
+    for dimension in guarantees
+        .iter()
+        .flat_map(|g| g.dimensions.iter())
+        .chain(evidence.iter().map(|e| &e.dimension))
+        .chain(demands.iter())
+    {
+        if let conserts_elements::dimension::Dimension::Numeric { uom: Some(uom), .. } = dimension {
+            if let Some(quantity) = uom.custom_quantity() {
+                if quantities.iter().all(|q| q.name != quantity.name) {
+                    quantities.push(quantity.clone());
+                }
+            }
+        }
+    }
+    quantities
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use conserts_elements::uom::UnitOfMeasure;
+    use conserts_error::UnitOfMeasureError;
+
+    #[test]
+    fn test_declarations() {
+        let declarations: Declarations = serde_yaml::from_str(
+            "
+quantities:
+  - name: PointDensity
+    units:
+      - name: point_per_square_meter
+        abbreviation: pts/m²
+        plural: points per square meter
+        factor: 1.0
+      - name: point_per_square_centimeter
+        abbreviation: pts/cm²
+        factor: 10000.0
+",
+        )
+        .unwrap();
+        let mut quantities = Quantities::default();
+        declarations.declare(&mut quantities).unwrap();
+        let uom = quantities.scope(|| UnitOfMeasure::new("points per square meter").unwrap());
+        assert_eq!(uom.Quantity(), "PointDensity");
+
+        let declarations: Declarations = serde_yaml::from_str(
+            "
+quantities:
+  - name: Brightness
+    units:
+      - name: point_per_square_meter
+        factor: 1.0
+",
+        )
+        .unwrap();
+        assert!(matches!(
+            declarations.declare(&mut quantities),
+            Err(ConSertError::UnitOfMeasure {
+                source: UnitOfMeasureError::Conflict { .. }
+            })
+        ));
+    }
+}
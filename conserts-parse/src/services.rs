@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: MIT
 
 use crate::get_functional_service_type;
-use crate::GetAttribute;
+use crate::{GetAttribute, Locate};
 use conserts_elements::elements::demands::Demand;
 use conserts_elements::elements::guarantees::Guarantee;
 use conserts_elements::elements::services::{
@@ -32,8 +32,10 @@ fn parse_provided_services(
     doc.descendants()
         .filter(|n| n.tag_name() == ExpandedName::from("providedServices"))
         .map(|n| {
-            let related_guarantuees: Vec<&str> =
-                n.try_get_attribute("guarantees")?.split(' ').collect();
+            let related_guarantuees: Vec<&str> = n
+                .try_get_attribute("guarantees")?
+                .split_whitespace()
+                .collect();
 
             let guarantees = related_guarantuees
                 .iter()
@@ -56,9 +58,10 @@ fn parse_provided_services(
                             ParsingError::WrongIndex("guarantee".to_string(), idx.to_string())
                                 .into()
                         })
-                        .map(|d| d.clone())
+                        .cloned()
                 })
-                .collect::<Result<Vec<Arc<Guarantee>>, ConSertError<Demand, RequiredService>>>()?;
+                .collect::<Result<Vec<Arc<Guarantee>>, ConSertError<Demand, RequiredService>>>()
+                .at(&n)?;
             let functional_service_type = get_functional_service_type(n)?;
             Ok(Rc::new(ProvidedService::new(
                 n.try_get_attribute("name")?,
@@ -77,7 +80,8 @@ fn parse_required_services(
     doc.descendants()
         .filter(|n| n.tag_name() == ExpandedName::from("requiredServices"))
         .map(|n| {
-            let related_demands: Vec<&str> = n.try_get_attribute("demands")?.split(' ').collect();
+            let related_demands: Vec<&str> =
+                n.try_get_attribute("demands")?.split_whitespace().collect();
 
             let demands = related_demands
                 .iter()
@@ -99,10 +103,10 @@ fn parse_required_services(
                         .ok_or_else(|| {
                             ParsingError::WrongIndex("demand".to_string(), idx.to_string()).into()
                         })
-                        .map(|d| d.clone())
+                        .cloned()
                 })
-                .collect::<Result<Vec<Arc<Mutex<Demand>>>, ConSertError<Demand, RequiredService>>>(
-                )?;
+                .collect::<Result<Vec<Arc<Mutex<Demand>>>, ConSertError<Demand, RequiredService>>>()
+                .at(&n)?;
 
             let functional_service_type = get_functional_service_type(n)?;
             Ok(Arc::new(RequiredService::new(
@@ -2,32 +2,95 @@
 //
 // SPDX-License-Identifier: MIT
 
+use crate::dimensions::{
+    dimensions_to_text, modulo_subset, text_to_dimensions, text_to_integrity_level,
+};
 use askama::Template;
 use conserts_elements::consert::Consert;
-use conserts_elements::elements::{consert_tree, evidence, guarantees};
+use conserts_elements::dimension::Dimension;
+use conserts_elements::elements::consert_tree::{ConsertTree, ConsertTreeElement, GateFunction};
+use conserts_elements::elements::{demands, evidence, guarantees, services};
+use conserts_elements::integrity::IntegrityLevel;
 use evidence::Evidence;
 use std::borrow::Borrow;
+use std::sync::{Arc, Mutex};
 
-struct GateTemplate {
-    index: String,
-    function: String,
+mod filters {
+    /// Escapes a value for an XML attribute. As the model is declared as ASCII, all other
+    /// characters are written as character references, as are line breaks.
+    pub fn attr<T: std::fmt::Display>(value: T) -> askama::Result<String> {
+        Ok(value
+            .to_string()
+            .chars()
+            .map(|c| match c {
+                '&' => "&amp;".to_string(),
+                '<' => "&lt;".to_string(),
+                '>' => "&gt;".to_string(),
+                '"' => "&quot;".to_string(),
+                '\'' => "&apos;".to_string(),
+                c if c.is_ascii() && !c.is_ascii_control() => c.to_string(),
+                c => format!("&#x{:X};", c as u32),
+            })
+            .collect())
+    }
 }
 
-impl<T> From<T> for GateTemplate
-where
-    T: Borrow<consert_tree::Gate>,
-{
-    fn from(gate: T) -> Self {
-        Self {
-            index: format!("{}", gate.borrow().index()),
-            function: format!("{:?}", gate.borrow().function()),
+/// The text safeTbox uses to describe an element, from which the dimensions and the integrity
+/// level are parsed. The description is kept if it describes both, otherwise the text is
+/// derived from them.
+pub(crate) fn refinement(
+    description: &Option<String>,
+    dimensions: &[Dimension],
+    integrity: Option<IntegrityLevel>,
+) -> String {
+    if let Some(description) = description {
+        if let (Ok(parsed), Ok(level)) = (
+            text_to_dimensions(description),
+            text_to_integrity_level(description),
+        ) {
+            if parsed.eq(&dimensions.iter().map(modulo_subset).collect::<Vec<_>>())
+                && level.eq(&integrity)
+            {
+                return description.clone();
+            }
         }
     }
+    let text = dimensions_to_text(dimensions)
+        // a level in the description is replaced by the actual one
+        .or_else(|| {
+            description.as_ref().map(|description| {
+                description
+                    .split(':')
+                    .next()
+                    .unwrap_or_default()
+                    .trim_end()
+                    .to_string()
+            })
+        })
+        .or_else(|| {
+            dimensions.first().map(|dimension| match dimension {
+                Dimension::Binary { r#type }
+                | Dimension::Categorical { r#type, .. }
+                | Dimension::Ordinal { r#type, .. }
+                | Dimension::Numeric { r#type, .. } => r#type.clone(),
+            })
+        })
+        .unwrap_or_default();
+    match integrity {
+        Some(level) => format!("{}: {}", text, level),
+        None => text,
+    }
+}
+
+struct GateTemplate {
+    name: String,
+    function: &'static str,
 }
 
 struct EvidenceTemplate {
     name: String,
     description: String,
+    refinement: String,
 }
 
 impl<T> From<T> for EvidenceTemplate
@@ -35,9 +98,19 @@ where
     T: AsRef<Evidence>,
 {
     fn from(evidence: T) -> Self {
+        let evidence = evidence.as_ref();
         Self {
-            name: evidence.as_ref().id.clone(),
-            description: evidence.as_ref().description.clone().unwrap_or_default(),
+            name: evidence.id.clone(),
+            description: evidence.description.clone().unwrap_or_default(),
+            // evidence has no integrity level of its own, the one of the description is kept
+            refinement: refinement(
+                &evidence.description,
+                std::slice::from_ref(&evidence.dimension),
+                evidence
+                    .description
+                    .as_ref()
+                    .and_then(|description| text_to_integrity_level(description).ok().flatten()),
+            ),
         }
     }
 }
@@ -45,6 +118,7 @@ where
 struct GuaranteeTemplate {
     name: String,
     description: String,
+    refinement: String,
 }
 
 impl<T> From<T> for GuaranteeTemplate
@@ -52,9 +126,53 @@ where
     T: AsRef<guarantees::Guarantee>,
 {
     fn from(guarantee: T) -> Self {
+        let guarantee = guarantee.as_ref();
+        Self {
+            name: guarantee.id.clone(),
+            description: guarantee.description.clone().unwrap_or_default(),
+            refinement: refinement(
+                &guarantee.description,
+                &guarantee.dimensions,
+                guarantee.integrity,
+            ),
+        }
+    }
+}
+
+struct DemandTemplate {
+    name: String,
+    description: String,
+    refinement: String,
+}
+
+impl From<&Arc<Mutex<demands::Demand>>> for DemandTemplate {
+    fn from(demand: &Arc<Mutex<demands::Demand>>) -> Self {
+        #![allow(clippy::unwrap_used)]
+        let demand = demand.lock().unwrap();
         Self {
-            name: guarantee.as_ref().id.clone(),
-            description: guarantee.as_ref().description.clone().unwrap_or_default(),
+            name: demand.id.clone(),
+            description: demand.description.clone().unwrap_or_default(),
+            refinement: refinement(&demand.description, &demand.dimensions, demand.integrity),
+        }
+    }
+}
+
+struct ServiceTemplate {
+    name: String,
+    elements: String,
+    functional_service_type: String,
+}
+
+impl ServiceTemplate {
+    fn new(name: &str, paths: Vec<String>, functional_service_type: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            elements: paths
+                .iter()
+                .map(|path| format!("//@configurations.0/@conSert/{}", path))
+                .collect::<Vec<_>>()
+                .join(" "),
+            functional_service_type: functional_service_type.to_string(),
         }
     }
 }
@@ -64,25 +182,102 @@ struct GuaranteePropagationTemplate {
     target: String,
 }
 
-impl<T> From<T> for GuaranteePropagationTemplate
-where
-    T: Borrow<guarantees::GuaranteePropagation>,
-{
-    fn from(guarantee_propagation: T) -> Self {
+/// Writes the ConSert trees as gates and guarantee propagations. Gates are numbered by their
+/// position in the model and shared gates are only written once. Tautologies and
+/// contradictions below the root become gates without inputs.
+struct TreeWriter<'a> {
+    consert: &'a Consert,
+    gate_ids: Vec<String>,
+    gates: Vec<GateTemplate>,
+    guarantee_propagations: Vec<GuaranteePropagationTemplate>,
+}
+
+impl<'a> TreeWriter<'a> {
+    fn new(consert: &'a Consert) -> Self {
         Self {
-            source: guarantee_propagation.borrow().source_path(),
-            target: guarantee_propagation.borrow().target_path(),
+            consert,
+            gate_ids: vec![],
+            gates: vec![],
+            guarantee_propagations: vec![],
+        }
+    }
+
+    fn add_guarantee(&mut self, index: usize, cst: &ConsertTree) {
+        if let ConsertTreeElement::Tautology = cst.data.element {
+            return;
         }
+        let source = self.add_tree(cst);
+        self.propagate(source, format!("@guarantees.{}", index));
     }
+
+    fn add_tree(&mut self, cst: &ConsertTree) -> String {
+        let node = &cst.data;
+        match &node.element {
+            ConsertTreeElement::RuntimeEvidence(index, evidence) => {
+                let index = position(&self.consert.evidence(), evidence).unwrap_or(*index);
+                format!("@runtimeEvidence.{}", index)
+            }
+            ConsertTreeElement::Demand(index, demand) => {
+                let index = position(&self.consert.demands(), demand).unwrap_or(*index);
+                format!("@demands.{}", index)
+            }
+            ConsertTreeElement::Tautology => {
+                self.add_gate(format!("Tautology_{}", self.gates.len()), GateFunction::And)
+            }
+            ConsertTreeElement::Contradiction => self.add_gate(
+                format!("Contradiction_{}", self.gates.len()),
+                GateFunction::Or,
+            ),
+            ConsertTreeElement::Gate(id, _, function) => {
+                match self.gate_ids.iter().position(|gate_id| gate_id.eq(id)) {
+                    Some(index) => format!("@gates.{}", index),
+                    None => {
+                        let path = self.add_gate(id.clone(), *function);
+                        for child in node.children.iter() {
+                            let source = self.add_tree(child);
+                            self.propagate(source, path.clone());
+                        }
+                        path
+                    }
+                }
+            }
+        }
+    }
+
+    fn add_gate(&mut self, id: String, function: GateFunction) -> String {
+        let path = format!("@gates.{}", self.gates.len());
+        self.gates.push(GateTemplate {
+            name: id.clone(),
+            function: match function {
+                GateFunction::And => "AND",
+                GateFunction::Or => "OR",
+            },
+        });
+        self.gate_ids.push(id);
+        path
+    }
+
+    fn propagate(&mut self, source: String, target: String) {
+        self.guarantee_propagations
+            .push(GuaranteePropagationTemplate { source, target });
+    }
+}
+
+fn position<T>(elements: &[Arc<T>], element: &Arc<T>) -> Option<usize> {
+    elements.iter().position(|e| Arc::ptr_eq(e, element))
 }
 
 #[derive(Template)]
-#[template(path = "consert.xml")]
+#[template(path = "consert.xml", escape = "none")]
 pub(crate) struct ConsertTemplate {
+    name: String,
     gates: Vec<GateTemplate>,
     guarantee_propagations: Vec<GuaranteePropagationTemplate>,
     guarantees: Vec<GuaranteeTemplate>,
+    demands: Vec<DemandTemplate>,
     evidence: Vec<EvidenceTemplate>,
+    provided_services: Vec<ServiceTemplate>,
+    required_services: Vec<ServiceTemplate>,
 }
 
 impl<T> From<T> for ConsertTemplate
@@ -92,23 +287,10 @@ where
     fn from(consert: T) -> Self {
         let consert = consert.borrow();
 
-        let (gates, guarantee_propagations): (Vec<_>, Vec<_>) = consert
-            .guarantees()
-            .iter()
-            .map(|g| g.gates_and_guarantee_propagations())
-            .unzip();
-
-        let gates = gates
-            .into_iter()
-            .flatten()
-            .map(|g| GateTemplate::from(&g))
-            .collect();
-
-        let guarantee_propagations = guarantee_propagations
-            .into_iter()
-            .flatten()
-            .map(|gp| GuaranteePropagationTemplate::from(&gp))
-            .collect();
+        let mut tree_writer = TreeWriter::new(consert);
+        for (index, guarantee) in consert.guarantees().iter().enumerate() {
+            tree_writer.add_guarantee(index, &guarantee.cst);
+        }
 
         let guarantees = consert
             .guarantees()
@@ -116,17 +298,54 @@ where
             .map(GuaranteeTemplate::from)
             .collect();
 
+        let demands = consert.demands().iter().map(DemandTemplate::from).collect();
+
         let evidence = consert
             .evidence()
             .iter()
             .map(EvidenceTemplate::from)
             .collect();
 
+        let provided_services = consert
+            .provided_services()
+            .iter()
+            .map(|service: &std::rc::Rc<services::ProvidedService>| {
+                let paths = service
+                    .guarantees
+                    .iter()
+                    .map(|guarantee| {
+                        let index =
+                            position(&consert.guarantees(), guarantee).unwrap_or(guarantee.index);
+                        format!("@guarantees.{}", index)
+                    })
+                    .collect();
+                ServiceTemplate::new(&service.ident, paths, &service.functional_service_type)
+            })
+            .collect();
+
+        let required_services = consert
+            .required_services()
+            .iter()
+            .map(|service| {
+                let paths = service
+                    .demands
+                    .iter()
+                    .filter_map(|demand| position(&consert.demands(), demand))
+                    .map(|index| format!("@demands.{}", index))
+                    .collect();
+                ServiceTemplate::new(&service.ident, paths, &service.functional_service_type)
+            })
+            .collect();
+
         Self {
-            gates,
-            guarantee_propagations,
+            name: consert.name(),
+            gates: tree_writer.gates,
+            guarantee_propagations: tree_writer.guarantee_propagations,
             guarantees,
+            demands,
             evidence,
+            provided_services,
+            required_services,
         }
     }
 }
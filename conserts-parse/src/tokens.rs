@@ -0,0 +1,282 @@
+// SPDX-FileCopyrightText: 2022 Fraunhofer Institute for Experimental Software Engineering IESE
+//
+// SPDX-License-Identifier: MIT
+
+use conserts_error::ParsingError;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Operator {
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+impl Operator {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Operator::Less => "<",
+            Operator::LessOrEqual => "<=",
+            Operator::Greater => ">",
+            Operator::GreaterOrEqual => ">=",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum TokenKind {
+    /// A run of characters without whitespace or punctuation, e.g. `Speed`, `km/h` or `ASIL-B`.
+    Word(String),
+    /// Text in double quotes, which may contain any character but `"`.
+    Quoted(String),
+    /// A number with a decimal point or a decimal comma, e.g. `-2,5`.
+    Number(f64),
+    Operator(Operator),
+    Colon,
+    Semicolon,
+    Comma,
+    OpenBrace,
+    CloseBrace,
+}
+
+impl fmt::Display for TokenKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokenKind::Word(word) => write!(f, "'{}'", word),
+            TokenKind::Quoted(text) => write!(f, "\"{}\"", text),
+            TokenKind::Number(number) => write!(f, "{}", number),
+            TokenKind::Operator(operator) => write!(f, "'{}'", operator.as_str()),
+            TokenKind::Colon => write!(f, "':'"),
+            TokenKind::Semicolon => write!(f, "';'"),
+            TokenKind::Comma => write!(f, "','"),
+            TokenKind::OpenBrace => write!(f, "'{{'"),
+            TokenKind::CloseBrace => write!(f, "'}}'"),
+        }
+    }
+}
+
+/// A token with its byte offsets in the text.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Token {
+    pub(crate) kind: TokenKind,
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+}
+
+impl Token {
+    pub(crate) fn is_word(&self, word: &str) -> bool {
+        matches!(&self.kind, TokenKind::Word(w) if w.eq_ignore_ascii_case(word))
+    }
+}
+
+/// The column of a byte offset, counted in characters from 1.
+pub(crate) fn column(text: &str, offset: usize) -> usize {
+    text[..offset].chars().count() + 1
+}
+
+pub(crate) fn error(text: &str, offset: usize, message: String) -> ParsingError {
+    ParsingError::InvalidDimension {
+        text: text.to_string(),
+        column: column(text, offset),
+        message,
+    }
+}
+
+fn is_punctuation(c: char) -> bool {
+    matches!(c, '<' | '>' | ':' | ';' | ',' | '{' | '}' | '"')
+}
+
+pub(crate) fn tokenize(text: &str) -> Result<Vec<Token>, ParsingError> {
+    let mut tokens = vec![];
+    let mut chars = text.char_indices().peekable();
+    // a minus starts a number only at the beginning of a word
+    let mut word_boundary = true;
+    // inside braces, a comma separates categories instead of decimals
+    let mut in_braces = false;
+
+    while let Some(&(start, c)) = chars.peek() {
+        let next_is_digit = text[start + c.len_utf8()..]
+            .chars()
+            .next()
+            .is_some_and(|n| n.is_ascii_digit());
+        let kind =
+            if c.is_whitespace() {
+                let _ = chars.next();
+                word_boundary = true;
+                continue;
+            } else if c.is_ascii_digit() || (c == '-' && next_is_digit && word_boundary) {
+                let _ = chars.next();
+                let mut separator = false;
+                while let Some(&(offset, n)) = chars.peek() {
+                    let followed_by_digit = text[offset + n.len_utf8()..]
+                        .chars()
+                        .next()
+                        .is_some_and(|d| d.is_ascii_digit());
+                    let decimal = n == '.' || (n == ',' && !in_braces);
+                    if n.is_ascii_digit() || (decimal && !separator && followed_by_digit) {
+                        separator |= !n.is_ascii_digit();
+                        let _ = chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                let end = chars.peek().map_or(text.len(), |(offset, _)| *offset);
+                let number = text[start..end].replace(',', ".");
+                TokenKind::Number(number.parse().map_err(|_| {
+                    error(text, start, format!("Could not parse number {}", number))
+                })?)
+            } else if c == '"' {
+                let _ = chars.next();
+                let content_start = start + 1;
+                let content_end = loop {
+                    match chars.next() {
+                        Some((offset, '"')) => break offset,
+                        Some(_) => {}
+                        None => return Err(error(text, start, "Unterminated quote".to_string())),
+                    }
+                };
+                TokenKind::Quoted(text[content_start..content_end].to_string())
+            } else if c == '<' || c == '>' {
+                let _ = chars.next();
+                let or_equal = chars.next_if(|(_, n)| *n == '=').is_some();
+                TokenKind::Operator(match (c, or_equal) {
+                    ('<', false) => Operator::Less,
+                    ('<', true) => Operator::LessOrEqual,
+                    ('>', false) => Operator::Greater,
+                    _ => Operator::GreaterOrEqual,
+                })
+            } else if is_punctuation(c) {
+                let _ = chars.next();
+                match c {
+                    ':' => TokenKind::Colon,
+                    ';' => TokenKind::Semicolon,
+                    ',' => TokenKind::Comma,
+                    '{' => TokenKind::OpenBrace,
+                    _ => TokenKind::CloseBrace,
+                }
+            } else {
+                while chars
+                    .next_if(|(_, n)| !n.is_whitespace() && !is_punctuation(*n))
+                    .is_some()
+                {}
+                let end = chars.peek().map_or(text.len(), |(offset, _)| *offset);
+                TokenKind::Word(text[start..end].to_string())
+            };
+        let end = chars.peek().map_or(text.len(), |(offset, _)| *offset);
+        word_boundary = !matches!(kind, TokenKind::Word(_) | TokenKind::Number(_));
+        match kind {
+            TokenKind::OpenBrace => in_braces = true,
+            TokenKind::CloseBrace => in_braces = false,
+            _ => {}
+        }
+        tokens.push(Token { kind, start, end });
+    }
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn kinds(text: &str) -> Vec<TokenKind> {
+        tokenize(text)
+            .unwrap()
+            .into_iter()
+            .map(|t| t.kind)
+            .collect()
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            kinds("Delay bound to < 300.5ms: ASIL-B"),
+            vec![
+                TokenKind::Word("Delay".into()),
+                TokenKind::Word("bound".into()),
+                TokenKind::Word("to".into()),
+                TokenKind::Operator(Operator::Less),
+                TokenKind::Number(300.5),
+                TokenKind::Word("ms".into()),
+                TokenKind::Colon,
+                TokenKind::Word("ASIL-B".into()),
+            ]
+        );
+        assert_eq!(
+            kinds("-2,5 km/h<=\"a<b\" in {A, B}"),
+            vec![
+                TokenKind::Number(-2.5),
+                TokenKind::Word("km/h".into()),
+                TokenKind::Operator(Operator::LessOrEqual),
+                TokenKind::Quoted("a<b".into()),
+                TokenKind::Word("in".into()),
+                TokenKind::OpenBrace,
+                TokenKind::Word("A".into()),
+                TokenKind::Comma,
+                TokenKind::Word("B".into()),
+                TokenKind::CloseBrace,
+            ]
+        );
+        // decimal comma only between digits, a single decimal separator
+        assert_eq!(
+            kinds("1,2,3 1.5.6"),
+            vec![
+                TokenKind::Number(1.2),
+                TokenKind::Comma,
+                TokenKind::Number(3.0),
+                TokenKind::Number(1.5),
+                TokenKind::Word(".6".into()),
+            ]
+        );
+        // commas within braces separate categories
+        assert_eq!(
+            kinds("{1,2, 3}"),
+            vec![
+                TokenKind::OpenBrace,
+                TokenKind::Number(1.0),
+                TokenKind::Comma,
+                TokenKind::Number(2.0),
+                TokenKind::Comma,
+                TokenKind::Number(3.0),
+                TokenKind::CloseBrace,
+            ]
+        );
+        // a minus within a word is part of the word, as is an equals sign
+        assert_eq!(kinds("x-5"), vec![TokenKind::Word("x-5".into())]);
+        assert_eq!(
+            kinds("healthy == 4"),
+            vec![
+                TokenKind::Word("healthy".into()),
+                TokenKind::Word("==".into()),
+                TokenKind::Number(4.0),
+            ]
+        );
+        // a semicolon separates dimensions
+        assert_eq!(
+            kinds("Approved;\"PL\" in {PL d}"),
+            vec![
+                TokenKind::Word("Approved".into()),
+                TokenKind::Semicolon,
+                TokenKind::Quoted("PL".into()),
+                TokenKind::Word("in".into()),
+                TokenKind::OpenBrace,
+                TokenKind::Word("PL".into()),
+                TokenKind::Word("d".into()),
+                TokenKind::CloseBrace,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_errors() {
+        assert_eq!(
+            tokenize("\"Speed < 5 m/s"),
+            Err(ParsingError::InvalidDimension {
+                text: "\"Speed < 5 m/s".into(),
+                column: 1,
+                message: "Unterminated quote".into()
+            })
+        );
+    }
+}
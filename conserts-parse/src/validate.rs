@@ -0,0 +1,615 @@
+// SPDX-FileCopyrightText: 2022 Fraunhofer Institute for Experimental Software Engineering IESE
+//
+// SPDX-License-Identifier: MIT
+
+use crate::convert::Format;
+use crate::dimensions::{text_to_dimension, text_to_dimensions};
+use crate::guarantees::{element_name, gate_function, refers_to_element};
+use crate::yml::{self, duplicates, Propagation, YamlConsertElements};
+use crate::{get_refinement, graph, location, ConsertElements, GetAttribute};
+use conserts_elements::consert_tree::Gate;
+use conserts_elements::uom::{CustomQuantity, Quantities};
+use conserts_elements::{
+    demands::Demand, evidence::Evidence, guarantees::Guarantee, services::RequiredService,
+};
+use conserts_error::{ConSertError, Location, ParsingError};
+use roxmltree::{Document, ExpandedName, Node};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_yaml::Value;
+use std::collections::BTreeSet;
+use std::convert::TryFrom;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// Errors make a model invalid, warnings point out problems the loader tolerates.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A problem of a model, located in the model file if possible.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Problem {
+    pub severity: Severity,
+    pub message: String,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+}
+
+impl Problem {
+    fn new(message: String, location: Option<Location>) -> Self {
+        Self {
+            severity: Severity::Error,
+            message,
+            line: location.map(|location| location.line),
+            column: location.map(|location| location.column),
+        }
+    }
+
+    fn warning(self) -> Self {
+        Self {
+            severity: Severity::Warning,
+            ..self
+        }
+    }
+
+    pub fn location(&self) -> Option<Location> {
+        Some(Location {
+            line: self.line?,
+            column: self.column?,
+        })
+    }
+}
+
+impl From<ParsingError> for Problem {
+    fn from(error: ParsingError) -> Self {
+        Problem::new(error.kind().to_string(), error.location())
+    }
+}
+
+impl From<ConSertError<Demand, RequiredService>> for Problem {
+    fn from(error: ConSertError<Demand, RequiredService>) -> Self {
+        match error {
+            ConSertError::Parsing { source } => source.into(),
+            ConSertError::UnitOfMeasure { source } => Problem::new(source.to_string(), None),
+            error => Problem::new(error.to_string(), None),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Report {
+    pub path: String,
+    pub valid: bool,
+    pub problems: Vec<Problem>,
+}
+
+/// Validates the model file, collecting all problems instead of stopping at the first one.
+#[cfg(not(tarpaulin_include))] // IO function
+pub fn validate<P: AsRef<Path>>(path: &P) -> Result<Report, ConSertError<Demand, RequiredService>> {
+    let format = Format::from_path(path).ok_or_else(|| {
+        ParsingError::Other("Only .xml /.model, .yml and .json supported".to_string())
+    })?;
+    let model = crate::read_model_file(path.as_ref())?;
+    let problems = validate_model(format, &model);
+    Ok(Report {
+        path: path.as_ref().display().to_string(),
+        valid: problems
+            .iter()
+            .all(|problem| problem.severity == Severity::Warning),
+        problems,
+    })
+}
+
+pub fn validate_model(format: Format, model: &str) -> Vec<Problem> {
+    match format {
+        Format::Xml => validate_xml(model),
+        Format::Yaml => match serde_yaml::from_str(model) {
+            Ok(value) => validate_yaml(value, model),
+            Err(error) => vec![yml::locate(error.into(), model).into()],
+        },
+        Format::Json => match serde_json::from_str::<serde_json::Value>(model)
+            .map_err(ConSertError::from)
+            .and_then(|value| Ok(serde_yaml::to_value(value)?))
+        {
+            Ok(value) => validate_yaml(value, model),
+            Err(error) => vec![yml::locate(error, model).into()],
+        },
+    }
+}
+
+/// The cycles formed by the edges. Once a cycle is found, its last edge is ignored to find
+/// the remaining ones.
+fn find_cycles(mut edges: Vec<(String, String)>) -> Vec<Vec<String>> {
+    let mut cycles = vec![];
+    while let Some(cycle) = graph::find_cycle(&edges) {
+        let last = (
+            cycle[cycle.len() - 2].clone(),
+            cycle[cycle.len() - 1].clone(),
+        );
+        edges.retain(|edge| edge.ne(&last));
+        cycles.push(cycle);
+    }
+    cycles
+}
+
+/// The elements of a YAML/JSON model are deserialized one by one, so that every invalid
+/// element is reported. The elements that could not be read are remembered to not report
+/// references to them as well.
+struct YamlValidator<'a> {
+    model: &'a str,
+    problems: Vec<Problem>,
+    invalid_ids: BTreeSet<String>,
+}
+
+impl<'a> YamlValidator<'a> {
+    fn elements<T: DeserializeOwned>(&mut self, value: &Value, key: &str) -> Vec<T> {
+        let items = match value.get(key) {
+            Some(Value::Sequence(items)) => items,
+            Some(_) => {
+                self.problems
+                    .push(Problem::new(format!("{} must be a list", key), None));
+                return vec![];
+            }
+            None => {
+                self.problems
+                    .push(Problem::new(format!("Missing list: {}", key), None));
+                return vec![];
+            }
+        };
+        items
+            .iter()
+            .enumerate()
+            .filter_map(
+                |(index, item)| match serde_yaml::from_value::<T>(item.clone()) {
+                    Ok(element) => Some(element),
+                    Err(error) => {
+                        let text = |key: &str| item.get(key).and_then(Value::as_str);
+                        let location = match (text("id"), text("from"), text("to")) {
+                            (Some(id), _, _) => {
+                                let _ = self.invalid_ids.insert(id.to_string());
+                                yml::find_entries(self.model, "id", id).next()
+                            }
+                            (None, Some(from), Some(to)) => {
+                                yml::find_propagation(self.model, from, to)
+                            }
+                            _ => None,
+                        };
+                        self.problems.push(Problem::new(
+                            format!("{}[{}]: {}", key, index, error),
+                            location,
+                        ));
+                        None
+                    }
+                },
+            )
+            .collect()
+    }
+
+    fn report(&mut self, error: ParsingError) {
+        let location = yml::location_of(&error, self.model);
+        self.problems.push(match location {
+            Some(location) => error.at(location).into(),
+            None => error.into(),
+        });
+    }
+}
+
+fn validate_yaml(value: Value, model: &str) -> Vec<Problem> {
+    let mut validator = YamlValidator {
+        model,
+        problems: vec![],
+        invalid_ids: BTreeSet::new(),
+    };
+    if !value.is_mapping() {
+        return vec![Problem::new(
+            "Invalid model: expected a mapping".to_string(),
+            None,
+        )];
+    }
+
+    // the quantities are needed to read the dimensions using their units, but are only in
+    // scope while doing so
+    let quantities = match value.get("quantities") {
+        Some(_) => validator.elements::<CustomQuantity>(&value, "quantities"),
+        None => vec![],
+    };
+    let mut declared = Quantities::current();
+    for quantity in quantities.iter() {
+        if let Err(error) = declared.declare(quantity.clone()) {
+            let location = yml::find_entries(model, "name", &quantity.name).next();
+            validator
+                .problems
+                .push(Problem::new(error.to_string(), location));
+        }
+    }
+    declared.scope(|| validate_yaml_elements(validator, &value, quantities))
+}
+
+fn validate_yaml_elements(
+    mut validator: YamlValidator,
+    value: &Value,
+    quantities: Vec<CustomQuantity>,
+) -> Vec<Problem> {
+    #![allow(clippy::unwrap_used)]
+    let model = validator.model;
+    let mut cse = YamlConsertElements {
+        guarantees: validator
+            .elements::<Guarantee>(value, "guarantees")
+            .into_iter()
+            .map(Arc::new)
+            .collect(),
+        evidence: validator
+            .elements::<Evidence>(value, "evidence")
+            .into_iter()
+            .map(Arc::new)
+            .collect(),
+        demands: validator
+            .elements::<Demand>(value, "demands")
+            .into_iter()
+            .map(|demand| Arc::new(Mutex::new(demand)))
+            .collect(),
+        gates: validator.elements::<Gate>(value, "gates"),
+        tree_propagations: validator.elements::<Propagation>(value, "tree_propagations"),
+        required_services: validator.elements(value, "required_services"),
+        provided_services: validator.elements(value, "provided_services"),
+        quantities,
+    };
+
+    let ids = cse
+        .guarantees
+        .iter()
+        .map(|g| g.id.clone())
+        .chain(cse.evidence.iter().map(|e| e.id.clone()))
+        .chain(cse.demands.iter().map(|d| d.lock().unwrap().id.clone()))
+        .chain(cse.gates.iter().map(|g| g.id.clone()))
+        .chain(validator.invalid_ids.iter().cloned());
+    for id in duplicates(ids) {
+        validator.report(ParsingError::NonUniqueIds(vec![id]));
+    }
+
+    let is_invalid = |id: &str| validator.invalid_ids.contains(id);
+    let errors = cse
+        .unknown_elements()
+        .into_iter()
+        .filter(|error| !matches!(error, ParsingError::UnknownElement { id, .. } if is_invalid(id)))
+        .chain(cse.unknown_references().into_iter().filter(
+            |error| !matches!(error, ParsingError::UnknownReference { id, .. } if is_invalid(id)),
+        ))
+        .chain(cse.invalid_dimensions())
+        .collect::<Vec<_>>();
+    for error in errors {
+        validator.report(error);
+    }
+
+    let edges = cse
+        .tree_propagations
+        .iter()
+        .map(|p| (p.from.clone(), p.to.clone()))
+        .collect();
+    for cycle in find_cycles(edges) {
+        validator.report(ParsingError::Cycle(cycle));
+    }
+
+    // building the ConSert finds what remains, e.g. elements missing in the trees
+    if validator.problems.is_empty() {
+        cse.assign_indices();
+        if let Err(error) = ConsertElements::try_from(cse) {
+            validator.problems.push(yml::locate(error, model).into());
+        }
+    }
+    validator.problems
+}
+
+fn nodes<'a>(doc: &'a Document, tag: &str) -> Vec<Node<'a, 'a>> {
+    doc.descendants()
+        .filter(|n| n.tag_name() == ExpandedName::from(tag))
+        .collect()
+}
+
+fn validate_xml(model: &str) -> Vec<Problem> {
+    let doc = match crate::parse_model(model) {
+        Ok(doc) => doc,
+        Err(error) => return vec![error.into()],
+    };
+    let mut problems: Vec<Problem> = vec![];
+    let mut warnings = vec![];
+    let mut check = |result: Result<(), ConSertError<Demand, RequiredService>>, node: &Node| {
+        if let Err(error) = result {
+            // also locates errors of the dimensions, e.g. unsupported units
+            let problem = Problem::from(error);
+            problems.push(match problem.location() {
+                Some(_) => problem,
+                None => Problem::new(problem.message, Some(location(node))),
+            });
+        }
+    };
+
+    for node in nodes(&doc, "runtimeEvidence") {
+        check(
+            crate::evidence::refinement(&node)
+                .and_then(|refinement| text_to_dimension(&refinement).map(|_| ())),
+            &node,
+        );
+    }
+    for node in nodes(&doc, "guarantees")
+        .into_iter()
+        .chain(nodes(&doc, "demands"))
+    {
+        check(
+            get_refinement(&node)
+                .and_then(|refinement| text_to_dimensions(&refinement).map(|_| ())),
+            &node,
+        );
+    }
+    for node in nodes(&doc, "gates") {
+        check(gate_function(&node).map(|_| ()), &node);
+    }
+
+    let names = nodes(&doc, "guarantees")
+        .into_iter()
+        .chain(nodes(&doc, "runtimeEvidence"))
+        .chain(nodes(&doc, "demands"))
+        .filter_map(|node| node.attribute("name").map(|name| (name, node)))
+        .collect::<Vec<_>>();
+    // elements of XML models are referred to by position, so the loader accepts them
+    for id in duplicates(names.iter().map(|(name, _)| *name)) {
+        let node = names.iter().filter(|(name, _)| name.eq(&id)).nth(1);
+        let node = node
+            .map(|(_, node)| *node)
+            .unwrap_or_else(|| doc.root_element());
+        warnings.push(
+            Problem::new(
+                ParsingError::NonUniqueIds(vec![id.to_string()])
+                    .kind()
+                    .to_string(),
+                Some(location(&node)),
+            )
+            .warning(),
+        );
+    }
+
+    let propagations = nodes(&doc, "guaranteePropagations")
+        .into_iter()
+        .filter_map(|node| {
+            let source = node.attribute("sourceElement");
+            let target = node.attribute("targetElement");
+            let (source, target) = match (source, target) {
+                (Some(source), Some(target)) => (source, target),
+                _ => {
+                    check(
+                        node.try_get_attribute("sourceElement")
+                            .and_then(|_| node.try_get_attribute("targetElement"))
+                            .map(|_| ())
+                            .map_err(ConSertError::from),
+                        &node,
+                    );
+                    return None;
+                }
+            };
+            let unknown = [source, target]
+                .iter()
+                .copied()
+                .find(|path| !refers_to_element(&doc, path));
+            if let Some(path) = unknown {
+                check(
+                    Err(ParsingError::UnknownElement {
+                        id: element_name(path).to_string(),
+                        from: element_name(source).to_string(),
+                        to: element_name(target).to_string(),
+                    }
+                    .into()),
+                    &node,
+                );
+            }
+            Some((element_name(source), element_name(target), node))
+        })
+        .collect::<Vec<_>>();
+
+    for (tag, attribute) in [
+        ("providedServices", "guarantees"),
+        ("requiredServices", "demands"),
+    ] {
+        for node in nodes(&doc, tag) {
+            let service = node.attribute("name").unwrap_or_default();
+            for path in node
+                .attribute(attribute)
+                .unwrap_or_default()
+                .split_whitespace()
+            {
+                let name = element_name(path);
+                if !(name.starts_with(&format!("@{}.", attribute)) && refers_to_element(&doc, path))
+                {
+                    check(
+                        Err(ParsingError::UnknownReference {
+                            service: service.to_string(),
+                            id: name.to_string(),
+                        }
+                        .into()),
+                        &node,
+                    );
+                }
+            }
+        }
+    }
+
+    let edges = propagations
+        .iter()
+        .map(|(source, target, _)| (source.to_string(), target.to_string()))
+        .collect();
+    for cycle in find_cycles(edges) {
+        let node = propagations
+            .iter()
+            .find(|(source, target, _)| source.eq(&cycle[0]) && target.eq(&cycle[1]))
+            .map(|(_, _, node)| *node)
+            .unwrap_or_else(|| doc.root_element());
+        check(Err(ParsingError::Cycle(cycle).into()), &node);
+    }
+
+    // building the ConSert finds what remains, e.g. missing attributes
+    if problems.is_empty() {
+        if let Err(error) = ConsertElements::try_from(&doc) {
+            problems.push(error.into());
+        }
+    }
+    problems.extend(warnings);
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(problems: &[Problem]) -> Vec<String> {
+        problems.iter().map(|p| p.message.clone()).collect()
+    }
+
+    #[test]
+    fn test_valid_models() {
+        for (format, path) in [
+            (Format::Yaml, "../models/FabOS_Scanner.yml"),
+            (Format::Yaml, "../models/FabOS_Scanner2.yml"),
+            (Format::Xml, "../models/DEIS_DemoFollowerTruckSystem.model"),
+            (Format::Xml, "../models/DEIS_DemoLeaderTruckSystem.model"),
+        ] {
+            let model = std::fs::read_to_string(path).unwrap();
+            assert_eq!(validate_model(format, &model), vec![], "{}", path);
+        }
+    }
+
+    #[test]
+    fn test_yml_problems() {
+        let model = std::fs::read_to_string("../models/FabOS_Scanner.yml")
+            .unwrap()
+            .replace("  - id: Force", "  - id: E_Approved")
+            .replace("from: Force", "from: E_Approved")
+            .replace("    to: G_Approved", "    to: G_Unknown")
+            .replace("      - Latency", "      - Bandwidth")
+            .replace("uom: meter", "uom: parsec")
+            .replace(
+                "- Inclusive:\n                start: 0.0\n                end: 5.0\n          subset: Guarantee",
+                "[]\n          subset: Guarantee",
+            );
+        let problems = validate_model(Format::Yaml, &model);
+        let messages = messages(&problems);
+        assert_eq!(problems.len(), 5, "{:#?}", problems);
+        assert!(messages[0].starts_with("evidence[0]: Unsupported unit: parsec"));
+        assert_eq!(
+            messages[1],
+            "demands[0]: A covered set of ranges must be non-empty."
+        );
+        assert_eq!(messages[2], "IDs are not unique: E_Approved");
+        assert_eq!(
+            messages[3],
+            "Propagation from E_Approved to G_Unknown refers to unknown element: G_Unknown"
+        );
+        assert_eq!(
+            messages[4],
+            "Service LatencyService refers to unknown element: Bandwidth"
+        );
+        assert_eq!(
+            problems[0].location(),
+            Some(Location {
+                line: 24,
+                column: 5
+            })
+        );
+        assert_eq!(
+            problems[2].location(),
+            Some(Location {
+                line: 40,
+                column: 5
+            })
+        );
+    }
+
+    #[test]
+    fn test_yml_empty_range() {
+        let model = std::fs::read_to_string("../models/FabOS_Scanner.yml")
+            .unwrap()
+            .replace(
+                "start: 0.0\n                end: 5.0\n          subset: Demand",
+                "start: 6.0\n                end: 5.0\n          subset: Demand",
+            );
+        let problems = validate_model(Format::Yaml, &model);
+        assert_eq!(
+            messages(&problems),
+            vec!["Dimension DistanceIsKept covers no values".to_string()]
+        );
+        assert_eq!(
+            problems[0].location(),
+            Some(Location {
+                line: 11,
+                column: 11
+            })
+        );
+    }
+
+    #[test]
+    fn test_yml_cycle() {
+        let model = std::fs::read_to_string("../models/FabOS_Scanner.yml")
+            .unwrap()
+            .replace(
+                "gates: []",
+                "gates:\n  - id: A\n    function: And\n  - id: B\n    function: Or",
+            )
+            .replace(
+                "tree_propagations:\n",
+                "tree_propagations:\n  - from: A\n    to: B\n  - from: B\n    to: A\n",
+            );
+        let problems = validate_model(Format::Yaml, &model);
+        assert_eq!(
+            messages(&problems),
+            vec!["Propagations form a cycle: A -> B -> A"]
+        );
+        assert!(problems[0].location().is_some());
+    }
+
+    #[test]
+    fn test_json_problems() {
+        let problems = validate_model(Format::Json, r#"{"guarantees": [], "evidence": []}"#);
+        assert_eq!(
+            messages(&problems),
+            vec![
+                "Missing list: demands",
+                "Missing list: gates",
+                "Missing list: tree_propagations",
+                "Missing list: required_services",
+                "Missing list: provided_services",
+            ]
+        );
+        let problems = validate_model(Format::Json, "{");
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].location().is_some());
+    }
+
+    #[test]
+    fn test_xml_problems() {
+        let model =
+            std::fs::read_to_string("../models/DEIS_DemoLeaderTruckSystemIncompatible.model")
+                .unwrap();
+        let problems = validate_model(Format::Xml, &model);
+        // the loader accepts duplicate names, so they are only a warning
+        assert_eq!(messages(&problems), vec!["IDs are not unique: RtE3"]);
+        assert_eq!(problems[0].severity, Severity::Warning);
+        assert_eq!(
+            problems[0].location(),
+            Some(Location {
+                line: 35,
+                column: 7
+            })
+        );
+
+        let model = std::fs::read_to_string("../models/DEIS_DemoFollowerTruckSystem.model")
+            .unwrap()
+            .replacen("@gates.1\"", "@gates.9\"", 1)
+            .replacen("gateType=\"AND\"", "gateType=\"XOR\"", 1);
+        let messages = messages(&validate_model(Format::Xml, &model));
+        assert_eq!(messages.len(), 2, "{:#?}", messages);
+        assert_eq!(messages[0], "Unsupported gate: xor");
+        assert!(messages[1].ends_with("refers to unknown element: @gates.9"));
+    }
+}
@@ -2,46 +2,59 @@
 //
 // SPDX-License-Identifier: MIT
 
+use crate::quantities::Declarations;
 use conserts_elements::{
-    consert_tree::{ConsertTree, ConsertTreeElement, Gate, GateFunction, Tree, TreeNode},
+    consert::Consert,
+    consert_tree::{ConsertTree, ConsertTreeElement, Gate, GateFunction, Subtrees, Tree},
     demands::Demand,
     elements,
     evidence::Evidence,
     guarantees::Guarantee,
+    uom::{CustomQuantity, Quantities},
 };
-use conserts_error::{ConSertError, ParsingError};
+use conserts_error::{ConSertError, Location, ParsingError};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::{
     collections::BTreeSet,
+    convert::TryFrom,
     rc::Rc,
     str::FromStr,
     sync::{Arc, Mutex},
 };
 
-fn has_unique_elements<T>(iter: T) -> bool
+pub(crate) fn duplicates<T>(iter: T) -> Vec<T::Item>
 where
     T: IntoIterator,
-    T::Item: Eq + Ord,
+    T::Item: Clone + Eq + Ord,
 {
     let mut uniq = BTreeSet::new();
-    iter.into_iter().all(move |x| uniq.insert(x))
+    let duplicates: BTreeSet<_> = iter
+        .into_iter()
+        .filter(|x| !uniq.insert(x.clone()))
+        .collect();
+    duplicates.into_iter().collect()
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, JsonSchema, Serialize)]
 pub struct RequiredService {
     pub id: String,
     pub functional_service_type: String,
     pub demands: Vec<String>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, JsonSchema, Serialize)]
 pub struct ProvidedService {
     pub id: String,
     pub functional_service_type: String,
     pub guarantees: Vec<String>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, JsonSchema, Serialize)]
+#[schemars(
+    title = "ConSert",
+    description = "A Conditional Safety Certificate in the YAML/JSON model format of conserts."
+)]
 pub struct YamlConsertElements {
     pub guarantees: Vec<Arc<Guarantee>>,
     pub evidence: Vec<Arc<Evidence>>,
@@ -50,6 +63,9 @@ pub struct YamlConsertElements {
     pub tree_propagations: Vec<Propagation>,
     pub required_services: Vec<RequiredService>,
     pub provided_services: Vec<ProvidedService>,
+    /// Quantities and their units that are not part of `uom::si`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub quantities: Vec<CustomQuantity>,
 }
 
 impl YamlConsertElements {
@@ -70,6 +86,7 @@ impl YamlConsertElements {
             conserts_elements::services::RequiredService,
         >,
     > {
+        let mut subtrees = Subtrees::default();
         self.guarantees
             .iter()
             .enumerate()
@@ -78,8 +95,9 @@ impl YamlConsertElements {
                     id: g.id.clone(),
                     description: g.description.clone(),
                     dimensions: g.dimensions.clone(),
+                    integrity: g.integrity,
                     index,
-                    cst: grow_cst(&g.id, self)?,
+                    cst: grow_cst(&g.id, self, &mut subtrees)?,
                 }))
             })
             .collect()
@@ -87,14 +105,14 @@ impl YamlConsertElements {
 
     pub(crate) fn provided_services(
         &self,
+        guarantees: &[Arc<Guarantee>],
     ) -> Vec<Rc<conserts_elements::services::ProvidedService>> {
         self.provided_services
             .iter()
             .map(|p| {
                 Rc::new(conserts_elements::services::ProvidedService {
                     ident: p.id.clone(),
-                    guarantees: self
-                        .guarantees
+                    guarantees: guarantees
                         .iter()
                         .filter(|g| p.guarantees.contains(&g.id))
                         .cloned()
@@ -126,6 +144,29 @@ impl YamlConsertElements {
             .collect::<Vec<_>>()
     }
 
+    /// Indices are not part of the YAML format, they are derived from the position of
+    /// the elements in their lists.
+    pub(crate) fn assign_indices(&mut self) {
+        #![allow(clippy::unwrap_used)]
+        self.evidence = self
+            .evidence
+            .iter()
+            .enumerate()
+            .map(|(index, e)| {
+                Arc::new(Evidence {
+                    index,
+                    ..e.as_ref().clone()
+                })
+            })
+            .collect();
+        for (index, demand) in self.demands.iter().enumerate() {
+            demand.lock().unwrap().index = index;
+        }
+        for (index, gate) in self.gates.iter_mut().enumerate() {
+            gate.index = index;
+        }
+    }
+
     pub(crate) fn unique_ids(
         &self,
     ) -> Result<
@@ -143,58 +184,367 @@ impl YamlConsertElements {
             .chain(self.demands.iter().map(|d| d.lock().unwrap().id.clone()))
             .chain(self.guarantees.iter().map(|g| g.id.clone()))
             .chain(self.gates.iter().map(|g| g.id.clone()));
-        if has_unique_elements(ids) {
+        let duplicates = duplicates(ids);
+        if duplicates.is_empty() {
             Ok(())
         } else {
-            Err(ParsingError::NonUniqueIds.into())
+            Err(ParsingError::NonUniqueIds(duplicates).into())
+        }
+    }
+
+    pub(crate) fn known_elements(
+        &self,
+    ) -> Result<
+        (),
+        ConSertError<
+            conserts_elements::demands::Demand,
+            conserts_elements::services::RequiredService,
+        >,
+    > {
+        match self.unknown_elements().into_iter().next() {
+            Some(error) => Err(error.into()),
+            None => Ok(()),
+        }
+    }
+
+    /// Propagations have to start at evidence, demands or gates and end at gates or guarantees.
+    pub(crate) fn unknown_elements(&self) -> Vec<ParsingError> {
+        #![allow(clippy::unwrap_used)]
+        let is_gate = |id: &str| self.gates.iter().any(|g| g.id == id);
+        let is_source = |id: &str| {
+            is_gate(id)
+                || self.evidence.iter().any(|e| e.id == id)
+                || self.demands.iter().any(|d| d.lock().unwrap().id == id)
+        };
+        let is_target = |id: &str| is_gate(id) || self.guarantees.iter().any(|g| g.id == id);
+        self.tree_propagations
+            .iter()
+            .filter_map(|propagation| {
+                let unknown = if !is_source(&propagation.from) {
+                    &propagation.from
+                } else if !is_target(&propagation.to) {
+                    &propagation.to
+                } else {
+                    return None;
+                };
+                Some(ParsingError::UnknownElement {
+                    id: unknown.clone(),
+                    from: propagation.from.clone(),
+                    to: propagation.to.clone(),
+                })
+            })
+            .collect()
+    }
+
+    pub(crate) fn valid_dimensions(
+        &self,
+    ) -> Result<
+        (),
+        ConSertError<
+            conserts_elements::demands::Demand,
+            conserts_elements::services::RequiredService,
+        >,
+    > {
+        match self.invalid_dimensions().into_iter().next() {
+            Some(error) => Err(error.into()),
+            None => Ok(()),
         }
     }
+
+    /// Dimensions have to be consistent in themselves, e.g. ordinal dimensions have to refer to
+    /// one of their declared levels and numeric dimensions have to cover some values.
+    pub(crate) fn invalid_dimensions(&self) -> Vec<ParsingError> {
+        #![allow(clippy::unwrap_used)]
+        let demands = self
+            .demands
+            .iter()
+            .flat_map(|d| d.lock().unwrap().dimensions.clone())
+            .collect::<Vec<_>>();
+        self.guarantees
+            .iter()
+            .flat_map(|g| g.dimensions.iter())
+            .chain(self.evidence.iter().map(|e| &e.dimension))
+            .chain(demands.iter())
+            .filter_map(|dimension| dimension.check().err())
+            .collect()
+    }
+
+    /// Services have to refer to guarantees and demands of the ConSert.
+    pub(crate) fn unknown_references(&self) -> Vec<ParsingError> {
+        #![allow(clippy::unwrap_used)]
+        let provided = self.provided_services.iter().flat_map(|p| {
+            p.guarantees
+                .iter()
+                .filter(|id| self.guarantees.iter().all(|g| g.id.ne(*id)))
+                .map(move |id| (&p.id, id))
+        });
+        let required = self.required_services.iter().flat_map(|r| {
+            r.demands
+                .iter()
+                .filter(|id| self.demands.iter().all(|d| d.lock().unwrap().id.ne(*id)))
+                .map(move |id| (&r.id, id))
+        });
+        provided
+            .chain(required)
+            .map(|(service, id)| ParsingError::UnknownReference {
+                service: service.clone(),
+                id: id.clone(),
+            })
+            .collect()
+    }
 }
 
+impl TryFrom<&Consert> for YamlConsertElements {
+    type Error = ConSertError<Demand, elements::services::RequiredService>;
+
+    fn try_from(consert: &Consert) -> Result<Self, Self::Error> {
+        #![allow(clippy::unwrap_used)]
+        // XML models may repeat names, which ids of the YAML format must not: repeated names
+        // get a numbered suffix, which the conversion reports as a changed id.
+        let mut ids = BTreeSet::new();
+        let originals = consert.guarantees();
+        let guarantees = originals
+            .iter()
+            .map(|g| match unique_id(&mut ids, &g.id) {
+                id if id.eq(&g.id) => g.clone(),
+                id => Arc::new(Guarantee {
+                    id,
+                    description: g.description.clone(),
+                    dimensions: g.dimensions.clone(),
+                    integrity: g.integrity,
+                    index: g.index,
+                    cst: g.cst.clone(),
+                }),
+            })
+            .collect::<Vec<_>>();
+        let evidence = consert
+            .evidence()
+            .iter()
+            .map(|e| match unique_id(&mut ids, &e.id) {
+                id if id.eq(&e.id) => e.clone(),
+                id => Arc::new(Evidence {
+                    id,
+                    ..e.as_ref().clone()
+                }),
+            })
+            .collect::<Vec<_>>();
+        let demands = consert
+            .demands()
+            .iter()
+            .map(|d| {
+                let demand = d.lock().unwrap();
+                match unique_id(&mut ids, &demand.id) {
+                    id if id.eq(&demand.id) => d.clone(),
+                    id => Arc::new(Mutex::new(Demand {
+                        id,
+                        ..demand.clone()
+                    })),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut flattener = TreeFlattener {
+            evidence: consert
+                .evidence()
+                .into_iter()
+                .zip(evidence.iter().map(|e| e.id.clone()))
+                .collect(),
+            demands: consert
+                .demands()
+                .into_iter()
+                .zip(demands.iter().map(|d| d.lock().unwrap().id.clone()))
+                .collect(),
+            ..TreeFlattener::default()
+        };
+        for (original, guarantee) in originals.iter().zip(guarantees.iter()) {
+            flattener.add_guarantee(original, &guarantee.id);
+        }
+
+        let required_services = consert
+            .required_services()
+            .iter()
+            .map(|r| RequiredService {
+                id: r.ident.clone(),
+                functional_service_type: r.functional_service_type.clone(),
+                demands: r.demands.iter().map(|d| flattener.demand_id(d)).collect(),
+            })
+            .collect();
+
+        let provided_services = consert
+            .provided_services()
+            .iter()
+            .map(|p| ProvidedService {
+                id: p.ident.clone(),
+                functional_service_type: p.functional_service_type.clone(),
+                guarantees: p
+                    .guarantees
+                    .iter()
+                    .map(|g| match originals.iter().position(|o| Arc::ptr_eq(o, g)) {
+                        Some(index) => guarantees[index].id.clone(),
+                        None => g.id.clone(),
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        let elements = Self {
+            guarantees,
+            evidence,
+            demands,
+            gates: flattener.gates,
+            tree_propagations: flattener.tree_propagations,
+            required_services,
+            provided_services,
+            quantities: crate::quantities::used_by(consert),
+        };
+        elements.unique_ids()?;
+        Ok(elements)
+    }
+}
+
+/// The id itself if it is not taken yet, otherwise the id with the first free numbered suffix.
+fn unique_id(ids: &mut BTreeSet<String>, id: &str) -> String {
+    #![allow(clippy::unwrap_used)]
+    let unique = (1..)
+        .map(|n| match n {
+            1 => id.to_string(),
+            n => format!("{}_{}", id, n),
+        })
+        .find(|candidate| !ids.contains(candidate))
+        .unwrap();
+    let _ = ids.insert(unique.clone());
+    unique
+}
+
+fn top_level_gate_id(guarantee_id: &str) -> String {
+    format!("TLG_{}", guarantee_id)
+}
+
+/// Turns the ConSert trees of guarantees back into the flat list of gates and
+/// propagations used by the YAML format. Gates shared between several trees are only
+/// emitted once, tautologies and contradictions become gates without inputs.
+#[derive(Default)]
+struct TreeFlattener {
+    /// Evidence and demands of the ConSert trees and the ids they are exported with.
+    evidence: Vec<(Arc<Evidence>, String)>,
+    demands: Vec<(Arc<Mutex<Demand>>, String)>,
+    gates: Vec<Gate>,
+    tree_propagations: Vec<Propagation>,
+    constants: usize,
+}
+
+impl TreeFlattener {
+    /// Adds the tree of the guarantee, which is exported with the given id.
+    fn add_guarantee(&mut self, guarantee: &Guarantee, id: &str) {
+        let root = &guarantee.cst.data;
+        match &root.element {
+            ConsertTreeElement::Tautology => {}
+            ConsertTreeElement::Gate(gate, _, GateFunction::And)
+                if gate.eq(&top_level_gate_id(&guarantee.id)) =>
+            {
+                for child in root.children.iter() {
+                    let source = self.add_tree(child);
+                    self.propagate(source, id);
+                }
+            }
+            _ => {
+                let source = self.add_tree(&guarantee.cst);
+                self.propagate(source, id);
+            }
+        }
+    }
+
+    fn add_tree(&mut self, cst: &ConsertTree) -> String {
+        #![allow(clippy::unwrap_used)]
+        let node = &cst.data;
+        match &node.element {
+            ConsertTreeElement::RuntimeEvidence(_, evidence) => self
+                .evidence
+                .iter()
+                .find(|(original, _)| Arc::ptr_eq(original, evidence))
+                .map_or(&evidence.id, |(_, id)| id)
+                .clone(),
+            ConsertTreeElement::Demand(_, demand) => self.demand_id(demand),
+            ConsertTreeElement::Tautology => self.add_constant(GateFunction::And),
+            ConsertTreeElement::Contradiction => self.add_constant(GateFunction::Or),
+            ConsertTreeElement::Gate(id, _, function) => {
+                if self.gates.iter().all(|g| g.id.ne(id)) {
+                    self.gates
+                        .push(Gate::new(id.clone(), self.gates.len(), *function));
+                    for child in node.children.iter() {
+                        let source = self.add_tree(child);
+                        self.propagate(source, id);
+                    }
+                }
+                id.clone()
+            }
+        }
+    }
+
+    fn demand_id(&self, demand: &Arc<Mutex<Demand>>) -> String {
+        #![allow(clippy::unwrap_used)]
+        match self
+            .demands
+            .iter()
+            .find(|(original, _)| Arc::ptr_eq(original, demand))
+        {
+            Some((_, id)) => id.clone(),
+            None => demand.lock().unwrap().id.clone(),
+        }
+    }
+
+    fn add_constant(&mut self, function: GateFunction) -> String {
+        let id = match function {
+            GateFunction::And => format!("Tautology_{}", self.constants),
+            GateFunction::Or => format!("Contradiction_{}", self.constants),
+        };
+        self.constants += 1;
+        self.gates
+            .push(Gate::new(id.clone(), self.gates.len(), function));
+        id
+    }
+
+    fn propagate(&mut self, from: String, to: &str) {
+        self.tree_propagations.push(Propagation {
+            from,
+            to: to.to_string(),
+        });
+    }
+}
+
+/// Grows the ConSert tree of an element. Elements feeding several gates or guarantees are
+/// grown once and shared, cycles in the propagations are reported as errors.
 pub fn grow_cst(
     id: &str,
     cse: &YamlConsertElements,
+    subtrees: &mut Subtrees,
 ) -> Result<
     ConsertTree,
     ConSertError<conserts_elements::demands::Demand, conserts_elements::services::RequiredService>,
 > {
-    let element = create_element(id, cse)?;
+    subtrees.grow(id, |subtrees| {
+        let element = create_element(id, cse)?;
 
-    let children = cse
-        .tree_propagations
-        .iter()
-        .filter(|gp| gp.to.eq(id))
-        .map(|gp| grow_cst(&gp.from, cse))
-        .collect::<Result<Vec<ConsertTree>, ConSertError<_, _>>>()?;
-
-    if let ConsertTreeElement::Gate(_, _, gate_function) = element {
-        match children.len() {
-            0 => match gate_function {
-                GateFunction::And => {
-                    return Ok(Tree {
-                        data: Box::new(TreeNode {
-                            element: ConsertTreeElement::Tautology,
-                            children: vec![],
-                        }),
-                    })
-                }
-                GateFunction::Or => {
-                    return Ok(Tree {
-                        data: Box::new(TreeNode {
-                            element: ConsertTreeElement::Contradiction,
-                            children: vec![],
-                        }),
-                    })
-                }
-            },
-            #[allow(clippy::unwrap_used)]
-            1 => return Ok(children.into_iter().next().unwrap()),
-            _ => {}
+        let children = cse
+            .tree_propagations
+            .iter()
+            .filter(|gp| gp.to.eq(id))
+            .map(|gp| grow_cst(&gp.from, cse, subtrees))
+            .collect::<Result<Vec<ConsertTree>, ConSertError<_, _>>>()?;
+
+        if let ConsertTreeElement::Gate(_, _, gate_function) = element {
+            match children.len() {
+                0 => match gate_function {
+                    GateFunction::And => return Ok(Tree::leaf(ConsertTreeElement::Tautology)),
+                    GateFunction::Or => return Ok(Tree::leaf(ConsertTreeElement::Contradiction)),
+                },
+                #[allow(clippy::unwrap_used)]
+                1 => return Ok(children.into_iter().next().unwrap()),
+                _ => {}
+            }
         }
-    }
 
-    Ok(Tree {
-        data: Box::new(TreeNode { element, children }),
+        Ok(Tree::node(element, children))
     })
 }
 
@@ -208,7 +558,7 @@ pub fn create_element(
     #![allow(clippy::unwrap_used)]
     if cse.guarantees.iter().any(|g| g.id == id) {
         Ok(ConsertTreeElement::Gate(
-            format!("TLG_{}", id),
+            top_level_gate_id(id),
             0,
             GateFunction::And,
         ))
@@ -232,12 +582,117 @@ impl FromStr for YamlConsertElements {
     type Err = ConSertError<Demand, elements::services::RequiredService>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let cse = serde_yaml::from_str(s)?;
+        let mut quantities = Quantities::current();
+        serde_yaml::from_str::<Declarations>(s)?.declare(&mut quantities)?;
+        let mut cse: YamlConsertElements = quantities.scope(|| serde_yaml::from_str(s))?;
+        cse.assign_indices();
+        Ok(cse)
+    }
+}
+
+impl YamlConsertElements {
+    /// The JSON format shares the structure of the YAML format.
+    pub(crate) fn from_json(
+        s: &str,
+    ) -> Result<Self, ConSertError<Demand, elements::services::RequiredService>> {
+        let mut quantities = Quantities::current();
+        serde_json::from_str::<Declarations>(s)?.declare(&mut quantities)?;
+        let mut cse: YamlConsertElements = quantities.scope(|| serde_json::from_str(s))?;
+        cse.assign_indices();
         Ok(cse)
     }
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+/// Serde only reports the location of syntax errors. Errors found while building the ConSert
+/// are located by searching the model for the ids they refer to.
+pub(crate) fn locate(
+    error: ConSertError<Demand, elements::services::RequiredService>,
+    model: &str,
+) -> ConSertError<Demand, elements::services::RequiredService> {
+    let syntax = |message: String, line: usize, column: usize| {
+        // the location is part of the error, not of its message
+        let message = match message.rsplit_once(" at line ") {
+            Some((message, _)) => message.to_string(),
+            None => message,
+        };
+        ParsingError::Syntax(message).at(Location { line, column })
+    };
+    match error {
+        ConSertError::SerdeYaml { source } => match source.location() {
+            Some(location) => syntax(source.to_string(), location.line(), location.column()).into(),
+            None => ParsingError::Syntax(source.to_string()).into(),
+        },
+        ConSertError::SerdeJson { source } if source.line() > 0 => {
+            syntax(source.to_string(), source.line(), source.column()).into()
+        }
+        ConSertError::Parsing { source } => match location_of(source.kind(), model) {
+            Some(location) => source.at(location).into(),
+            None => source.into(),
+        },
+        error => error,
+    }
+}
+
+/// Searches the model for the element an error refers to.
+pub(crate) fn location_of(error: &ParsingError, model: &str) -> Option<Location> {
+    match error {
+        // the second occurrence is the duplicate
+        ParsingError::NonUniqueIds(ids) => ids
+            .first()
+            .and_then(|id| find_entries(model, "id", id).nth(1)),
+        ParsingError::UnknownElement { from, to, .. } => find_propagation(model, from, to),
+        ParsingError::MissingElement(id) => find_entries(model, "from", id)
+            .next()
+            .or_else(|| find_entries(model, "to", id).next()),
+        ParsingError::UnknownReference { service, .. } => find_entries(model, "id", service).next(),
+        ParsingError::UnknownLevel { level, .. } => find_entries(model, "level", level).next(),
+        ParsingError::EmptyRange(r#type) => find_entries(model, "type", r#type).next(),
+        ParsingError::Cycle(cycle) => match cycle.as_slice() {
+            [from, to, ..] => find_propagation(model, from, to),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Location of the propagation from `from` to `to`, or of the first one from `from`.
+pub(crate) fn find_propagation(model: &str, from: &str, to: &str) -> Option<Location> {
+    let lines = model.lines().collect::<Vec<_>>();
+    find_entries(model, "from", from)
+        .find(|location| {
+            lines
+                .get(location.line)
+                .is_some_and(|line| entry(line, "to", to).is_some())
+        })
+        .or_else(|| find_entries(model, "from", from).next())
+}
+
+/// Locations of the lines with `key: value`, in YAML or JSON notation.
+pub(crate) fn find_entries<'a>(
+    model: &'a str,
+    key: &'a str,
+    value: &'a str,
+) -> impl Iterator<Item = Location> + 'a {
+    model.lines().enumerate().filter_map(move |(index, line)| {
+        entry(line, key, value).map(|column| Location {
+            line: index + 1,
+            column,
+        })
+    })
+}
+
+fn entry(line: &str, key: &str, value: &str) -> Option<usize> {
+    let unquote = |s: &str| s.trim().trim_matches(|c| c == '"' || c == '\'').to_string();
+    let content = line.trim_start().trim_start_matches("- ");
+    let (k, v) = content.split_once(':')?;
+    if unquote(k) == key && unquote(v.trim_end_matches(',')) == value {
+        Some(line.len() - content.len() + 1)
+    } else {
+        None
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema, Serialize)]
 pub struct Propagation {
     pub from: String,
     pub to: String,
@@ -270,6 +725,7 @@ mod tests {
                 subset: SubsetRelationship::Guarantee,
                 uom: Some(UnitOfMeasure::new("ms").unwrap()),
             }],
+            integrity: None,
         }))];
         let guarantees = vec![
             Arc::new(Guarantee {
@@ -282,6 +738,7 @@ mod tests {
                     subset: SubsetRelationship::Demand,
                     uom: Some(UnitOfMeasure::new("mm").unwrap()),
                 }],
+                integrity: None,
                 cst: Default::default(),
             }),
             Arc::new(Guarantee {
@@ -291,6 +748,7 @@ mod tests {
                 dimensions: vec![Dimension::Binary {
                     r#type: "InstallationApproved".into(),
                 }],
+                integrity: None,
                 cst: Default::default(),
             }),
         ];
@@ -369,6 +827,7 @@ mod tests {
             gates,
             provided_services,
             required_services,
+            quantities: vec![],
         };
         /*std::fs::write(
             "../models/FabOS_Scanner.yml",
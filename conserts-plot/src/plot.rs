@@ -7,7 +7,8 @@
 
 use conserts_elements::consert::Consert;
 use conserts_elements::demands::Demand;
-use conserts_elements::dimension::{Dimension, SubsetRelationship};
+use conserts_elements::dimension::{Dimension, OrdinalBound, SubsetRelationship};
+use conserts_elements::integrity::IntegrityLevel;
 use conserts_elements::numeric_range::NumericRange;
 use conserts_elements::services::RequiredService;
 use conserts_error::ConSertError;
@@ -67,6 +68,8 @@ fn range_to_string(range: &NumericRange) -> String {
     match range {
         NumericRange::Exclusive(r) => format!("{:?}", r),
         NumericRange::Inclusive(r) => format!("{:?}", r),
+        NumericRange::LeftOpen(r) => format!("({}, {}]", r.start, r.end),
+        NumericRange::RightOpen(r) => format!("[{}, {})", r.start, r.end),
     }
 }
 
@@ -92,6 +95,23 @@ fn dim_to_string(dimension: &Dimension) -> String {
                 subset_to_string(subset),
             )
         }
+        Dimension::Ordinal {
+            r#type,
+            levels,
+            level,
+            bound,
+            subset,
+        } => format!(
+            "{}\n{} {} [{}] ({})",
+            r#type,
+            match bound {
+                OrdinalBound::AtLeast => ">=",
+                OrdinalBound::AtMost => "<=",
+            },
+            level,
+            levels.join(" < "),
+            subset_to_string(subset),
+        ),
         Dimension::Numeric {
             r#type: _,
             covered,
@@ -112,6 +132,10 @@ fn dim_to_string(dimension: &Dimension) -> String {
     }
 }
 
+fn integrity_to_string(integrity: &Option<IntegrityLevel>) -> String {
+    integrity.map_or("".to_string(), |level| format!("\n\n[{}]", level))
+}
+
 impl ConsertWrapper {
     fn from_consert(consert: &Consert) -> Self {
         #![allow(clippy::unwrap_used)]
@@ -136,13 +160,14 @@ impl ConsertWrapper {
                     Node {
                         id: g.id.clone(),
                         label: format!(
-                            "<Guarantee>\n\n{}\n\n{}",
+                            "<Guarantee>\n\n{}\n\n{}{}",
                             g.description.clone().unwrap_or_else(|| g.id.clone()),
                             g.dimensions
                                 .iter()
                                 .map(dim_to_string)
                                 .collect::<Vec<_>>()
-                                .join("\n\n")
+                                .join("\n\n"),
+                            integrity_to_string(&g.integrity)
                         ),
                     },
                 ))
@@ -172,13 +197,14 @@ impl ConsertWrapper {
                     Node {
                         id: id.clone(),
                         label: format!(
-                            "<Demand>\n\n{}\n\n{}",
+                            "<Demand>\n\n{}\n\n{}{}",
                             d.description.clone().unwrap_or_else(|| id.clone()),
                             d.dimensions
                                 .iter()
                                 .map(dim_to_string)
                                 .collect::<Vec<_>>()
-                                .join("\n\n")
+                                .join("\n\n"),
+                            integrity_to_string(&d.integrity)
                         ),
                     },
                 )